@@ -0,0 +1,49 @@
+use std::env;
+use std::process::ExitCode;
+use plwordnet::{PlWordNet, Query};
+
+
+const USAGE: &str = "Usage: plwordnet-query [--synsets] <plwordnet.xml> <term>...";
+
+fn main() -> ExitCode {
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    let synsets = args.first().is_some_and(|arg| arg == "--synsets");
+    if synsets {
+        args.remove(0);
+    }
+    if args.is_empty() {
+        eprintln!("{}", USAGE);
+        return ExitCode::FAILURE;
+    }
+    let path = args.remove(0);
+    // the shell has already split the terms, so each argument is one term
+    let query = match Query::from_terms(&args) {
+        Ok(query) => query,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        },
+    };
+    let wordnet = match PlWordNet::from_file(&path) {
+        Ok(wordnet) => wordnet,
+        Err(e) => {
+            eprintln!("{}: {}", path, e);
+            return ExitCode::FAILURE;
+        },
+    };
+
+    if synsets {
+        for synset in wordnet.query_synsets(&query) {
+            let units: Vec<_> = synset.lexical_units.iter()
+                .map(|lu| format!("{}.{}", lu.name, lu.variant))
+                .collect();
+            println!("{}\t{}\t{}", synset.id, units.join(", "), synset.definition);
+        }
+    } else {
+        for lu in wordnet.query_lexical_units(&query) {
+            let synset = lu.synset_id.map_or(String::new(), |id| id.to_string());
+            println!("{}\t{}.{}\t{}\t{}\t{}", lu.id, lu.name, lu.variant, lu.pos, lu.domain, synset);
+        }
+    }
+    ExitCode::SUCCESS
+}
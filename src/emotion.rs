@@ -0,0 +1,250 @@
+use crate::gloss::{Gloss, GlossAnnotation};
+use crate::model::{LexicalUnitView, PlWordNet};
+
+
+/// Represents the sentiment polarity assigned to a lexical unit by an annotator.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Polarity {
+    /// Indicates a positive unit (`+`).
+    Positive,
+    /// Indicates a negative unit (`-`).
+    Negative,
+    /// Indicates an ambivalent unit (`amb`).
+    Ambivalent,
+    /// Indicates a neutral unit (`0`).
+    Neutral,
+}
+
+/// Represents the strength of a positive or negative polarity.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Strength {
+    /// Indicates a moderate polarity (`m`).
+    Moderate,
+    /// Indicates a strong polarity (`s`).
+    Strong,
+}
+
+/// Represents one of Plutchik's basic emotions used in plWordNet emotive annotation.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Emotion {
+    /// Indicates joy (`radość`).
+    Joy,
+    /// Indicates trust (`zaufanie`).
+    Trust,
+    /// Indicates anticipation (`cieszenie się na coś oczekiwanego`).
+    Anticipation,
+    /// Indicates surprise (`zaskoczenie czymś nieprzewidywanym`).
+    Surprise,
+    /// Indicates sadness (`smutek`).
+    Sadness,
+    /// Indicates fear (`strach`).
+    Fear,
+    /// Indicates anger (`złość`).
+    Anger,
+    /// Indicates disgust (`wstręt`).
+    Disgust,
+}
+
+/// Represents one of the fundamental human values used in plWordNet emotive annotation.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Valuation {
+    /// Indicates utility (`użyteczność`).
+    Utility,
+    /// Indicates another's good (`dobro drugiego człowieka`).
+    AnothersGood,
+    /// Indicates truth (`prawda`).
+    Truth,
+    /// Indicates knowledge (`wiedza`).
+    Knowledge,
+    /// Indicates beauty (`piękno`).
+    Beauty,
+    /// Indicates happiness (`szczęście`).
+    Happiness,
+    /// Indicates futility (`nieużyteczność`).
+    Futility,
+    /// Indicates harm (`krzywda`).
+    Harm,
+    /// Indicates ignorance (`niewiedza`).
+    Ignorance,
+    /// Indicates error (`błąd`).
+    Error,
+    /// Indicates ugliness (`brzydota`).
+    Ugliness,
+    /// Indicates misfortune (`nieszczęście`).
+    Misfortune,
+}
+
+/// Represents the emotive annotation of a lexical unit made by a single annotator, parsed from
+/// description markup such as `##A1: {radość; zaufanie} {piękno} + m [Lubię zamki.]`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmotionAnnotation<'a> {
+    /// Label of the annotation, e.g. `"A1"`, identifying the annotator.
+    pub annotator: &'a str,
+    pub polarity: Option<Polarity>,
+    /// Strength of the polarity, only present for positive and negative units.
+    pub strength: Option<Strength>,
+    pub emotions: Vec<Emotion>,
+    pub valuations: Vec<Valuation>,
+    pub examples: Vec<&'a str>,
+}
+
+impl<'a> From<&GlossAnnotation<'a>> for EmotionAnnotation<'a> {
+    fn from(annotation: &GlossAnnotation<'a>) -> Self {
+        let mut emotive = EmotionAnnotation {
+            annotator: annotation.label,
+            polarity: None,
+            strength: None,
+            emotions: Vec::new(),
+            valuations: Vec::new(),
+            examples: Vec::new(),
+        };
+        let mut marks = String::new();
+        let mut rest = annotation.text;
+        while let Some(c) = rest.chars().next() {
+            let close = match c {
+                '{' => '}',
+                '[' => ']',
+                _ => {
+                    if !c.is_whitespace() {
+                        marks.push(c);
+                    }
+                    rest = &rest[c.len_utf8()..];
+                    continue;
+                },
+            };
+            let end = rest.find(close).unwrap_or(rest.len());
+            let inner = &rest[1..end];
+            if c == '[' {
+                emotive.examples.push(inner.trim());
+            } else {
+                for name in inner.split([';', ',']).map(|name| name.trim().to_lowercase()) {
+                    if let Some(emotion) = parse_emotion(&name) {
+                        emotive.emotions.push(emotion);
+                    } else if let Some(valuation) = parse_valuation(&name) {
+                        emotive.valuations.push(valuation);
+                    }
+                }
+            }
+            rest = &rest[(end + 1).min(rest.len())..];
+        }
+        (emotive.polarity, emotive.strength) = parse_polarity(&marks);
+        emotive
+    }
+}
+
+impl<'a> LexicalUnitView<'a> {
+    /// Parses the emotive annotations in the description of this lexical unit.
+    ///
+    /// # Returns
+    ///
+    /// A vector of `EmotionAnnotation` instances, one per annotator, empty if the lexical unit
+    /// has not been annotated.
+    pub fn emotions(&self) -> Vec<EmotionAnnotation<'a>> {
+        self.gloss()
+            .annotations
+            .iter()
+            .map(EmotionAnnotation::from)
+            .collect()
+    }
+}
+
+impl PlWordNet {
+    /// Returns the lexical units that at least one annotator assigned the specified polarity.
+    ///
+    /// # Arguments
+    ///
+    /// * `polarity` - The polarity of the lexical units to retrieve.
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields `LexicalUnitView` instances ordered by id.
+    pub fn lexical_units_with_polarity(&self, polarity: Polarity) -> impl Iterator<Item=LexicalUnitView<'_>> {
+        self.polarity_index.get(&polarity)
+            .into_iter()
+            .flatten()
+            .filter_map(|&id| self.get_lexical_unit(id))
+    }
+
+    /// Returns the lexical units that at least one annotator associated with the specified
+    /// emotion.
+    ///
+    /// # Arguments
+    ///
+    /// * `emotion` - The emotion of the lexical units to retrieve.
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields `LexicalUnitView` instances ordered by id.
+    pub fn lexical_units_with_emotion(&self, emotion: Emotion) -> impl Iterator<Item=LexicalUnitView<'_>> {
+        self.emotion_index.get(&emotion)
+            .into_iter()
+            .flatten()
+            .filter_map(|&id| self.get_lexical_unit(id))
+    }
+}
+
+/// Fills the polarity and emotion indexes from the descriptions of all lexical units.
+pub(crate) fn build_emotion_indexes(wn: &mut PlWordNet) {
+    for lu in wn.lexical_units.values() {
+        for annotation in Gloss::parse(&lu.desc).annotations.iter().map(EmotionAnnotation::from) {
+            if let Some(polarity) = annotation.polarity {
+                wn.polarity_index.entry(polarity).or_default().push(lu.id);
+            }
+            for emotion in annotation.emotions {
+                wn.emotion_index.entry(emotion).or_default().push(lu.id);
+            }
+        }
+    }
+    for ids in wn.polarity_index.values_mut().chain(wn.emotion_index.values_mut()) {
+        ids.sort();
+        ids.dedup();
+    }
+}
+
+fn parse_polarity(marks: &str) -> (Option<Polarity>, Option<Strength>) {
+    let (polarity, strength) = match marks {
+        "amb" => return (Some(Polarity::Ambivalent), None),
+        "0" => return (Some(Polarity::Neutral), None),
+        _ if marks.starts_with('+') => (Polarity::Positive, &marks[1..]),
+        _ if marks.starts_with('-') => (Polarity::Negative, &marks[1..]),
+        _ => return (None, None),
+    };
+    let strength = match strength {
+        "m" => Some(Strength::Moderate),
+        "s" => Some(Strength::Strong),
+        _ => None,
+    };
+    (Some(polarity), strength)
+}
+
+fn parse_emotion(name: &str) -> Option<Emotion> {
+    match name {
+        "radość" => Some(Emotion::Joy),
+        "zaufanie" => Some(Emotion::Trust),
+        "smutek" => Some(Emotion::Sadness),
+        "strach" => Some(Emotion::Fear),
+        "złość" => Some(Emotion::Anger),
+        "wstręt" => Some(Emotion::Disgust),
+        _ if name.starts_with("cieszenie się") => Some(Emotion::Anticipation),
+        _ if name.starts_with("zaskoczenie") => Some(Emotion::Surprise),
+        _ => None,
+    }
+}
+
+fn parse_valuation(name: &str) -> Option<Valuation> {
+    match name {
+        "użyteczność" => Some(Valuation::Utility),
+        "prawda" => Some(Valuation::Truth),
+        "wiedza" => Some(Valuation::Knowledge),
+        "piękno" => Some(Valuation::Beauty),
+        "szczęście" => Some(Valuation::Happiness),
+        "nieużyteczność" => Some(Valuation::Futility),
+        "krzywda" => Some(Valuation::Harm),
+        "niewiedza" => Some(Valuation::Ignorance),
+        "błąd" => Some(Valuation::Error),
+        "brzydota" => Some(Valuation::Ugliness),
+        "nieszczęście" => Some(Valuation::Misfortune),
+        _ if name.starts_with("dobro") => Some(Valuation::AnothersGood),
+        _ => None,
+    }
+}
@@ -0,0 +1,148 @@
+use crate::model::LexicalUnitView;
+
+
+/// Represents the structured content of a lexical unit description, parsed from plWordNet markup
+/// such as `##K: pot. ##D: budowla obronna. [##W: Zwiedzaliśmy zamek.]`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Gloss<'a> {
+    /// Definition introduced with `##D:`.
+    pub definition: Option<&'a str>,
+    /// Qualifiers introduced with `##K:`, such as `pot.` or `przest.`.
+    pub qualifiers: Vec<&'a str>,
+    /// Usage examples enclosed in `[##W: ...]`, `[##P: ...]` and similar.
+    pub examples: Vec<GlossExample<'a>>,
+    /// Links introduced with `##L:` or enclosed in `{##L: ...}`.
+    pub links: Vec<&'a str>,
+    /// Emotive annotations introduced with `##A1:`, `##A2:` and so on.
+    pub annotations: Vec<GlossAnnotation<'a>>,
+    /// Fragments of the description which are not recognised as any of the above.
+    pub remainder: Vec<&'a str>,
+}
+
+/// Represents a usage example in a lexical unit description.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GlossExample<'a> {
+    pub text: &'a str,
+    /// Marker the example was introduced with, e.g. `"W"` for `[##W: ...]`.
+    pub source: &'a str,
+}
+
+/// Represents an emotive annotation in a lexical unit description.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GlossAnnotation<'a> {
+    /// Marker the annotation was introduced with, e.g. `"A1"`, identifying the annotator.
+    pub label: &'a str,
+    /// Unparsed annotation body, e.g. `"{ radość; użyteczność } + m [Lubię zamki.]"`.
+    pub text: &'a str,
+}
+
+impl<'a> Gloss<'a> {
+    /// Parses plWordNet description markup.
+    ///
+    /// # Arguments
+    ///
+    /// * `desc` - The description of a lexical unit.
+    ///
+    /// # Returns
+    ///
+    /// A `Gloss` borrowing its fragments from `desc`.
+    pub fn parse(desc: &'a str) -> Self {
+        let mut gloss = Gloss::default();
+        let mut rest = desc;
+        while !rest.is_empty() {
+            let (consumed, segment) = next_segment(rest);
+            match segment {
+                Segment::Enclosed('[', marker, text) => gloss.examples.push(GlossExample { text, source: marker }),
+                Segment::Enclosed(_, "L", text) | Segment::Marked("L", text) => gloss.links.push(text),
+                Segment::Marked("D", text) if gloss.definition.is_none() => gloss.definition = Some(text),
+                Segment::Marked("K", text) => gloss.qualifiers.extend(text.split_whitespace()),
+                Segment::Marked(label, text) if is_annotation(label) => {
+                    gloss.annotations.push(GlossAnnotation { label, text })
+                },
+                Segment::Enclosed(_, _, _) | Segment::Marked(_, _) | Segment::Text => {
+                    let raw = rest[..consumed].trim();
+                    if !raw.is_empty() {
+                        gloss.remainder.push(raw);
+                    }
+                },
+            }
+            rest = &rest[consumed..];
+        }
+        gloss
+    }
+}
+
+impl<'a> LexicalUnitView<'a> {
+    /// Parses the description of this lexical unit into a `Gloss`.
+    pub fn gloss(&self) -> Gloss<'a> {
+        Gloss::parse(self.desc)
+    }
+}
+
+enum Segment<'a> {
+    /// `[##X: text]` or `{##X: text}`, with the opening bracket and the marker.
+    Enclosed(char, &'a str, &'a str),
+    /// `##X: text`, running up to the next marker.
+    Marked(&'a str, &'a str),
+    /// Text outside of any marker.
+    Text,
+}
+
+/// Splits off the first segment of `text`, returning its length in bytes.
+fn next_segment(text: &str) -> (usize, Segment<'_>) {
+    if let Some(open) = text.chars().next().filter(|&c| c == '[' || c == '{') {
+        if text[1..].starts_with("##") {
+            let close = if open == '[' { ']' } else { '}' };
+            let end = matching_close(text, open, close).unwrap_or(text.len());
+            let inner = &text[3..end];
+            let consumed = (end + 1).min(text.len());
+            return match inner.split_once(':') {
+                Some((marker, body)) => (consumed, Segment::Enclosed(open, marker.trim(), body.trim())),
+                None => (consumed, Segment::Text),
+            };
+        }
+    }
+    if let Some(body) = text.strip_prefix("##") {
+        if let Some((marker, _)) = body.split_once(':').filter(|(m, _)| is_marker(m)) {
+            let start = 2 + marker.len() + 1;
+            let end = start + next_marker(&text[start..]);
+            return (end, Segment::Marked(marker, text[start..end].trim()));
+        }
+    }
+    let first = text.chars().next().map_or(0, char::len_utf8);
+    let end = first + next_marker(&text[first..]);
+    (end, Segment::Text)
+}
+
+/// Offset of the next `##`, `[##` or `{##` in `text`, or its length if there is none.
+fn next_marker(text: &str) -> usize {
+    match text.find("##") {
+        Some(index) if index > 0 && matches!(text.as_bytes()[index - 1], b'[' | b'{') => index - 1,
+        Some(index) => index,
+        None => text.len(),
+    }
+}
+
+/// Offset of the bracket closing the one at the start of `text`.
+fn matching_close(text: &str, open: char, close: char) -> Option<usize> {
+    let mut depth = 0;
+    for (index, c) in text.char_indices() {
+        if c == open {
+            depth += 1;
+        } else if c == close {
+            depth -= 1;
+            if depth == 0 {
+                return Some(index);
+            }
+        }
+    }
+    None
+}
+
+fn is_marker(marker: &str) -> bool {
+    !marker.is_empty() && marker.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+fn is_annotation(label: &str) -> bool {
+    label.strip_prefix('A').is_some_and(|n| n.chars().all(|c| c.is_ascii_digit()))
+}
@@ -0,0 +1,62 @@
+use std::collections::HashSet;
+use crate::Language;
+use crate::logic::synset_language;
+use crate::model::{PlWordNet, RelationTypeView, SynsetView};
+
+
+impl PlWordNet {
+    /// Retrieves the Princeton WordNet synsets connected to the synset with the specified ID by an
+    /// interlingual relation, such as interlingual synonymy or hyponymy.
+    ///
+    /// # Arguments
+    ///
+    /// * `synset_id` - The identifier of the synset whose English equivalents are retrieved.
+    ///
+    /// # Returns
+    ///
+    /// A vector of `(SynsetView, RelationTypeView)` pairs, where the relation type describes how
+    /// the given synset relates to the English one.
+    pub fn english_equivalents(&self, synset_id: usize) -> Vec<(SynsetView<'_>, RelationTypeView<'_>)> {
+        self.equivalents(synset_id, Language::EN)
+    }
+
+    /// Retrieves the Polish synsets connected to the synset with the specified ID by an
+    /// interlingual relation, such as interlingual synonymy or hyponymy.
+    ///
+    /// # Arguments
+    ///
+    /// * `synset_id` - The identifier of the synset whose Polish equivalents are retrieved.
+    ///
+    /// # Returns
+    ///
+    /// A vector of `(SynsetView, RelationTypeView)` pairs, where the relation type describes how
+    /// the given synset relates to the Polish one.
+    pub fn polish_equivalents(&self, synset_id: usize) -> Vec<(SynsetView<'_>, RelationTypeView<'_>)> {
+        self.equivalents(synset_id, Language::PL)
+    }
+
+    /// Synsets of the target language related to a synset of the other language. Incoming
+    /// relations are reported through their reverse relation type, when it exists.
+    fn equivalents(&self, synset_id: usize, target: Language) -> Vec<(SynsetView<'_>, RelationTypeView<'_>)> {
+        let language = |id| self.synsets.get(&id).and_then(|synset| synset_language(self, synset));
+        if language(synset_id).is_none_or(|language| language == target) {
+            return Vec::new();
+        }
+        let outgoing = self.synset_edges(synset_id, |_| true, |_| false)
+            .map(|(id, i)| (id, self.synset_relations[i].relation));
+        let incoming = self.synset_edges(synset_id, |_| false, |_| true)
+            .map(|(id, i)| {
+                let relation = self.synset_relations[i].relation;
+                let reverse = self.relation_types.get(&relation)
+                    .map(|rt| rt.reverse)
+                    .filter(|reverse| self.relation_types.contains_key(reverse));
+                (id, reverse.unwrap_or(relation))
+            });
+        let mut seen = HashSet::new();
+        outgoing.chain(incoming)
+            .filter(|&(id, _)| language(id) == Some(target))
+            .filter(|&pair| seen.insert(pair))
+            .filter_map(|(id, relation)| Some((self.get_synset(id)?, self.get_relation_type(relation)?)))
+            .collect()
+    }
+}
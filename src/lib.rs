@@ -1,18 +1,46 @@
 mod model;
 mod parser;
 mod logic;
+mod emotion;
+mod gloss;
+mod interlingual;
+mod path;
+mod query;
+mod search;
+mod similarity;
+mod taxonomy;
+mod traversal;
+mod workstate;
 
 #[cfg(test)]
 mod tests;
 
 
 pub use model::{
+    Domain,
     Language,
+    PartOfSpeech,
     PlWordNet,
+    PlWordNetError,
     Metadata,
     LexicalUnitView,
     SynsetView,
     LexicalRelationView,
     SynsetRelationView,
-    RelationTypeView
+    RelationTypeView,
+    RelationTypeTestView,
+    Workstate
+};
+pub use emotion::{Emotion, EmotionAnnotation, Polarity, Strength, Valuation};
+pub use gloss::{Gloss, GlossAnnotation, GlossExample};
+pub use path::RelationStep;
+pub use query::{Query, QueryError};
+pub use search::{LemmaMatch, SearchField, SearchMatch};
+pub use similarity::{InformationContent, Similarity};
+pub use workstate::LoadOptions;
+pub use traversal::{
+    Direction,
+    TraversalOrder,
+    Traversal,
+    TraversalIter
 };
@@ -1,17 +1,27 @@
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
 use std::fmt::{Debug, Display, Formatter};
 use crate::Language;
 use crate::model::{
+    Domain,
+    PartOfSpeech,
     PlWordNet,
+    PlWordNetError,
     Metadata,
     LexicalUnitView,
     LexicalUnit,
     SynsetView,
     Synset,
+    SynsetRelation,
     SynsetRelationView,
+    LexicalRelation,
     LexicalRelationView,
     RelationType,
-    RelationTypeView
+    RelationTypeView,
+    RelationTypeTestView,
+    Workstate
 };
+use crate::traversal::EdgeFilter;
 
 
 impl Debug for Language {
@@ -32,13 +42,198 @@ impl Display for Language {
     }
 }
 
+impl Display for PlWordNetError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PlWordNetError::Io(e) => write!(f, "I/O error: {}", e),
+            PlWordNetError::UnsupportedCompression(format) =>
+                write!(f, "input is {} compressed, but the `{}` feature is not enabled", format, format),
+            PlWordNetError::Xml { position, source } =>
+                write!(f, "XML error at position {}: {}", position, source),
+            PlWordNetError::BadAttribute { tag, attribute, value } =>
+                write!(f, "invalid value {:?} for attribute `{}` of <{}>", value, attribute, tag),
+            PlWordNetError::UnknownElement { position, tag } =>
+                write!(f, "unknown element <{}> at position {}", tag, position),
+            PlWordNetError::MissingRoot => write!(f, "missing <array-list> root element"),
+            PlWordNetError::DanglingContext { position, tag } =>
+                write!(f, "element <{}> at position {} is outside of its parent element", tag, position),
+            PlWordNetError::BadFrequencyLine { line, content } =>
+                write!(f, "malformed frequency line {}: {:?}", line, content),
+        }
+    }
+}
+
+impl Error for PlWordNetError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            PlWordNetError::Io(e) => Some(e),
+            PlWordNetError::Xml { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl Display for PartOfSpeech {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PartOfSpeech::Noun => write!(f, "noun"),
+            PartOfSpeech::Verb => write!(f, "verb"),
+            PartOfSpeech::Adjective => write!(f, "adjective"),
+            PartOfSpeech::Adverb => write!(f, "adverb"),
+        }
+    }
+}
+
+impl Display for Workstate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl Workstate {
+    /// Parses a workstate from its name in plWordNet.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the workstate, e.g. `"Sprawdzone"`.
+    ///
+    /// # Returns
+    ///
+    /// The matching `Workstate`, or `Workstate::Other` holding the name if it is not recognised.
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "Nie przetworzone" => Workstate::Unprocessed,
+            "Częściowo przetworzone" => Workstate::PartiallyProcessed,
+            "Sprawdzone" => Workstate::Verified,
+            "Błąd" => Workstate::Error,
+            name => Workstate::Other(name.to_owned()),
+        }
+    }
+
+    /// Returns the name of the workstate used in plWordNet, e.g. `"Sprawdzone"`.
+    pub fn name(&self) -> &str {
+        match self {
+            Workstate::Unprocessed => "Nie przetworzone",
+            Workstate::PartiallyProcessed => "Częściowo przetworzone",
+            Workstate::Verified => "Sprawdzone",
+            Workstate::Error => "Błąd",
+            Workstate::Other(name) => name,
+        }
+    }
+}
+
+impl Display for Domain {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
+
+/// Code, Polish label, English label and PWN lexicographer file of every domain.
+const DOMAINS: &[(Domain, &str, &str, &str, Option<&str>)] = &[
+    (Domain::Tops, "bhp", "najwyższe w hierarchii", "top concepts", Some("noun.Tops")),
+    (Domain::Acts, "czy", "czynności", "acts and actions", Some("noun.act")),
+    (Domain::Artifacts, "wytw", "wytwory ludzkie", "artifacts", Some("noun.artifact")),
+    (Domain::Attributes, "cech", "cechy ludzi i zwierząt", "attributes of people and animals", Some("noun.attribute")),
+    (Domain::BodyParts, "czc", "części ciała", "body parts", Some("noun.body")),
+    (Domain::Cognition, "umy", "związane z myśleniem", "cognition", Some("noun.cognition")),
+    (Domain::Communication, "por", "związane z porozumiewaniem się", "communication", Some("noun.communication")),
+    (Domain::Events, "zdarz", "zdarzenia", "events", Some("noun.event")),
+    (Domain::Feelings, "czuj", "uczucia, odczucia i emocje", "feelings and emotions", Some("noun.feeling")),
+    (Domain::Food, "jedz", "jedzenie", "food", Some("noun.food")),
+    (Domain::Groups, "grp", "grupy ludzi i rzeczy", "groups of people and things", Some("noun.group")),
+    (Domain::Locations, "msc", "miejsca i umiejscowienie", "places and locations", Some("noun.location")),
+    (Domain::Motives, "cel", "cel działania", "goals of actions", Some("noun.motive")),
+    (Domain::Objects, "rz", "obiekty naturalne", "natural objects", Some("noun.object")),
+    (Domain::People, "os", "ludzie", "people", Some("noun.person")),
+    (Domain::Phenomena, "zj", "zjawiska naturalne", "natural phenomena", Some("noun.phenomenon")),
+    (Domain::Plants, "rsl", "nazwy roślin", "plants", Some("noun.plant")),
+    (Domain::Possession, "pos", "posiadanie i jego zmiana", "possession and its transfer", Some("noun.possession")),
+    (Domain::Processes, "prc", "procesy naturalne", "natural processes", Some("noun.process")),
+    (Domain::Quantities, "il", "ilość, liczebność, jednostki miary", "quantities and units of measure", Some("noun.quantity")),
+    (Domain::Animals, "zw", "zwierzęta", "animals", Some("noun.animal")),
+    (Domain::Taxonomy, "sys", "systematyka, klasyfikacja", "taxonomy and classification", None),
+    (Domain::Unspecified, "wsz", "wszystkie", "no particular domain", None),
+    (Domain::States, "st", "sytuacje statyczne (stany)", "static situations (states)", Some("noun.state")),
+    (Domain::Substances, "sbst", "substancje", "substances", Some("noun.substance")),
+    (Domain::Time, "czas", "czas i stosunki czasowe", "time and temporal relations", Some("noun.time")),
+    (Domain::Relations, "zwz", "związek między ludźmi, rzeczami lub ideami", "relations between people, things or ideas", Some("noun.relation")),
+    (Domain::Shapes, "ksz", "kształty", "shapes", Some("noun.shape")),
+    (Domain::BodyCare, "hig", "pielęgnacja ciała", "body care", Some("verb.body")),
+    (Domain::Change, "zmn", "zmiana wielkości, temperatury, natężenia, itp.", "change of size, temperature, intensity and the like", Some("verb.change")),
+    (Domain::VerbsOfThinking, "cumy", "czasowniki myślenia", "verbs of thinking", Some("verb.cognition")),
+    (Domain::VerbsOfSpeaking, "cpor", "czasowniki mówienia, śpiewania itp.", "verbs of speaking, singing and the like", Some("verb.communication")),
+    (Domain::Competition, "wal", "czasowniki rywalizacji fizycznej", "verbs of physical competition", Some("verb.competition")),
+    (Domain::Consumption, "cjedz", "czasowniki jedzenia", "verbs of eating", Some("verb.consumption")),
+    (Domain::Contact, "dtk", "czasowniki kontaktu fizycznego", "verbs of physical contact", Some("verb.contact")),
+    (Domain::Creation, "cwytw", "czasowniki tworzenia", "verbs of creation", Some("verb.creation")),
+    (Domain::VerbsOfFeeling, "cczuj", "czasowniki wyrażające uczucia", "verbs of feeling", Some("verb.emotion")),
+    (Domain::Motion, "ruch", "czasowniki ruchu", "verbs of motion", Some("verb.motion")),
+    (Domain::Perception, "pst", "czasowniki postrzegania", "verbs of perception", Some("verb.perception")),
+    (Domain::VerbsOfPossession, "cpos", "czasowniki posiadania i zmiany posiadania", "verbs of possession and its transfer", Some("verb.possession")),
+    (Domain::Social, "sp", "czasowniki społeczne", "verbs of social interaction", Some("verb.social")),
+    (Domain::Stative, "cst", "czasowniki stanowe", "stative verbs", Some("verb.stative")),
+    (Domain::Weather, "pog", "czasowniki oznaczające zjawiska pogodowe", "verbs of weather phenomena", Some("verb.weather")),
+    (Domain::Qualitative, "jak", "przymiotniki jakościowe", "qualitative adjectives", Some("adj.all")),
+    (Domain::Relational, "rel", "przymiotniki relacyjne", "relational adjectives", Some("adj.pert")),
+    (Domain::Deverbal, "odcz", "przymiotniki odczasownikowe", "deverbal adjectives", None),
+];
+
+impl Domain {
+    /// Parses a domain from its plWordNet code.
+    ///
+    /// # Arguments
+    ///
+    /// * `code` - The short code of the domain, e.g. `"zw"`.
+    ///
+    /// # Returns
+    ///
+    /// An `Option` containing the `Domain`, or `None` if the code is not recognised.
+    pub fn from_code(code: &str) -> Option<Self> {
+        DOMAINS.iter()
+            .find(|(_, c, ..)| *c == code)
+            .map(|(domain, ..)| *domain)
+    }
+
+    /// Returns an iterator over every domain.
+    pub fn all() -> impl Iterator<Item=Domain> {
+        DOMAINS.iter().map(|(domain, ..)| *domain)
+    }
+
+    /// Returns the short code of the domain used in plWordNet, e.g. `"zw"`.
+    pub fn code(self) -> &'static str {
+        self.row().1
+    }
+
+    /// Returns the Polish description of the domain, e.g. `"zwierzęta"`.
+    pub fn polish_label(self) -> &'static str {
+        self.row().2
+    }
+
+    /// Returns the English description of the domain, e.g. `"animals"`.
+    pub fn english_label(self) -> &'static str {
+        self.row().3
+    }
+
+    /// Returns the name of the equivalent Princeton WordNet lexicographer file, e.g.
+    /// `"noun.animal"`, or `None` if there is no equivalent.
+    pub fn lexicographer_file(self) -> Option<&'static str> {
+        self.row().4
+    }
+
+    fn row(self) -> &'static (Domain, &'static str, &'static str, &'static str, Option<&'static str>) {
+        DOMAINS.iter()
+            .find(|(domain, ..)| *domain == self)
+            .expect("every domain is listed in DOMAINS")
+    }
+}
+
 impl PlWordNet {
     /// Retrieves the metadata of the PlWordNet instance.
     ///
     /// This method returns a `Metadata` struct containing information about the `PlWordNet`
     /// instance, such as the owner, date, version, and counts of elements contained within
     /// such as lexical units, synsets, relation types, lexical relations, and synset relations.
-    pub fn get_metadata(&self) -> Metadata {
+    pub fn get_metadata(&self) -> Metadata<'_> {
         Metadata {
             owner: &self.owner,
             date: &self.date,
@@ -61,7 +256,7 @@ impl PlWordNet {
     ///
     /// An `Option` containing the `LexicalUnitView` if a lexical unit with the given ID is found,
     /// or `None` if no lexical unit exists with the specified ID.
-    pub fn get_lexical_unit(&self, id: usize) -> Option<LexicalUnitView> {
+    pub fn get_lexical_unit(&self, id: usize) -> Option<LexicalUnitView<'_>> {
         self.lexical_units.get(&id).map(move |lu| lu.into())
     }
 
@@ -74,10 +269,75 @@ impl PlWordNet {
     ///
     /// An iterator that yields `LexicalUnitView` instances representing the lexical units in the
     /// plWordNet.
-    pub fn iter_lexical_units(&self) -> impl Iterator<Item=LexicalUnitView> {
+    pub fn iter_lexical_units(&self) -> impl Iterator<Item=LexicalUnitView<'_>> {
         self.lexical_units.values().map(move |lu| lu.into())
     }
 
+    /// Returns the lexical units whose written form is exactly `lemma`, ordered by variant.
+    ///
+    /// # Arguments
+    ///
+    /// * `lemma` - The written form of the lexical units to retrieve.
+    /// * `pos` - An optional part of speech restricting the results.
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields `LexicalUnitView` instances for every matching lexical unit.
+    pub fn lexical_units_by_lemma(
+        &self,
+        lemma: &str,
+        pos: Option<PartOfSpeech>,
+    ) -> impl Iterator<Item=LexicalUnitView<'_>> {
+        self.lemma_unit_ids(lemma)
+            .iter()
+            .filter_map(|&id| self.get_lexical_unit(id))
+            .filter(move |lu| pos.is_none_or(|pos| lu.part_of_speech == Some(pos)))
+    }
+
+    /// Ids of the lexical units whose written form is exactly `lemma`, ordered by variant.
+    pub(crate) fn lemma_unit_ids(&self, lemma: &str) -> &[usize] {
+        match self.sorted_lemmas.binary_search_by(|other| other.as_str().cmp(lemma)) {
+            Ok(i) => &self.lemma_index[i],
+            Err(_) => &[],
+        }
+    }
+
+    /// Returns the lexical units belonging to the specified semantic domain.
+    ///
+    /// # Arguments
+    ///
+    /// * `domain` - The semantic domain of the lexical units to retrieve.
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields `LexicalUnitView` instances ordered by id.
+    pub fn lexical_units_in_domain(&self, domain: Domain) -> impl Iterator<Item=LexicalUnitView<'_>> {
+        self.domain_index.get(&domain)
+            .into_iter()
+            .flatten()
+            .filter_map(|&id| self.get_lexical_unit(id))
+    }
+
+    /// Returns the synsets containing a lexical unit whose written form is exactly `lemma`,
+    /// ordered by the variant of that lexical unit.
+    ///
+    /// # Arguments
+    ///
+    /// * `lemma` - The written form of the lexical units whose synsets are retrieved.
+    /// * `pos` - An optional part of speech restricting the results.
+    ///
+    /// # Returns
+    ///
+    /// A vector of `SynsetView` instances, each synset appearing at most once.
+    pub fn synsets_by_lemma(&self, lemma: &str, pos: Option<PartOfSpeech>) -> Vec<SynsetView<'_>> {
+        let mut seen = HashSet::new();
+        self.lexical_units_by_lemma(lemma, pos)
+            .filter_map(|lu| lu.synset_id)
+            .filter(|&id| seen.insert(id))
+            .filter_map(|id| self.get_synset(id))
+            .collect()
+    }
+
     /// Retrieves a synset with the specified ID from the plWordNet.
     ///
     /// # Arguments
@@ -88,8 +348,24 @@ impl PlWordNet {
     ///
     /// An `Option` containing the `SynsetView` if a synset with the given ID is found,
     /// or `None` if no synset exists with the specified ID.
-    pub fn get_synset(&self, id: usize) -> Option<SynsetView> {
-        self.synsets.get(&id).map(|s| synset_to_view(&self, s))
+    pub fn get_synset(&self, id: usize) -> Option<SynsetView<'_>> {
+        self.synsets.get(&id).map(|s| synset_to_view(self, s))
+    }
+
+    /// Retrieves the synset containing the lexical unit with the specified ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `lu_id` - The identifier of the lexical unit whose synset is retrieved.
+    ///
+    /// # Returns
+    ///
+    /// An `Option` containing the `SynsetView` of the containing synset, or `None` if the lexical
+    /// unit does not exist or does not belong to any synset.
+    pub fn synset_of(&self, lu_id: usize) -> Option<SynsetView<'_>> {
+        self.lexical_units.get(&lu_id)
+            .and_then(|lu| lu.synset)
+            .and_then(|id| self.get_synset(id))
     }
 
     /// Returns an iterator over the synsets in the plWordNet.
@@ -99,8 +375,8 @@ impl PlWordNet {
     /// # Returns
     ///
     /// An iterator that yields `SynsetView` instances representing the synsets in the plWordNet.
-    pub fn iter_synsets(&self) -> impl Iterator<Item=SynsetView> {
-        self.synsets.values().map(move |s| synset_to_view(&self, s))
+    pub fn iter_synsets(&self) -> impl Iterator<Item=SynsetView<'_>> {
+        self.synsets.values().map(move |s| synset_to_view(self, s))
     }
 
     /// Returns an iterator over the lexical relations in the plWordNet.
@@ -112,14 +388,58 @@ impl PlWordNet {
     ///
     /// An iterator that yields `LexicalRelationView` instances representing the lexical relations in
     /// the plWordNet.
-    pub fn iter_lexical_relations(&self) -> impl Iterator<Item=LexicalRelationView> {
-        self.lexical_relations.iter().map(|lr| LexicalRelationView {
-            parent: self.get_lexical_unit(lr.parent),
-            child: self.get_lexical_unit(lr.child),
-            relation: self.get_relation_type(lr.relation),
-            valid: lr.valid,
-            owner: &lr.owner,
-        })
+    pub fn iter_lexical_relations(&self) -> impl Iterator<Item=LexicalRelationView<'_>> {
+        self.lexical_relations.iter().map(|lr| lexical_relation_to_view(self, lr))
+    }
+
+    /// Returns the lexical relations whose parent is the lexical unit with the specified ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `lu_id` - The identifier of the lexical unit the relations originate from.
+    /// * `relations` - The relation type IDs restricting the results, or none for every type.
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields `LexicalRelationView` instances for each outgoing relation.
+    pub fn lexical_relations_from(
+        &self,
+        lu_id: usize,
+        relations: impl IntoIterator<Item=usize>,
+    ) -> impl Iterator<Item=LexicalRelationView<'_>> {
+        self.lexical_relations_by_index(&self.lexical_relations_out, lu_id, EdgeFilter::from_relations(relations))
+    }
+
+    /// Returns the lexical relations whose child is the lexical unit with the specified ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `lu_id` - The identifier of the lexical unit the relations point to.
+    /// * `relations` - The relation type IDs restricting the results, or none for every type.
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields `LexicalRelationView` instances for each incoming relation.
+    pub fn lexical_relations_to(
+        &self,
+        lu_id: usize,
+        relations: impl IntoIterator<Item=usize>,
+    ) -> impl Iterator<Item=LexicalRelationView<'_>> {
+        self.lexical_relations_by_index(&self.lexical_relations_in, lu_id, EdgeFilter::from_relations(relations))
+    }
+
+    fn lexical_relations_by_index<'a>(
+        &'a self,
+        index: &'a HashMap<usize, Vec<usize>>,
+        lu_id: usize,
+        relations: EdgeFilter,
+    ) -> impl Iterator<Item=LexicalRelationView<'a>> {
+        index.get(&lu_id)
+            .into_iter()
+            .flatten()
+            .map(|&i| &self.lexical_relations[i])
+            .filter(move |lr| relations.accepts(lr.relation))
+            .map(|lr| lexical_relation_to_view(self, lr))
     }
 
     /// Returns an iterator over the synset relations in the plWordNet.
@@ -131,14 +451,58 @@ impl PlWordNet {
     ///
     /// An iterator that yields `SynsetRelationView` instances representing the synset relations in
     /// the plWordNet.
-    pub fn iter_synset_relations(&self) -> impl Iterator<Item=SynsetRelationView> {
-        self.synset_relations.iter().map(|lr| SynsetRelationView {
-            parent: self.get_synset(lr.parent),
-            child: self.get_synset(lr.child),
-            relation: self.get_relation_type(lr.relation),
-            valid: lr.valid,
-            owner: &lr.owner,
-        })
+    pub fn iter_synset_relations(&self) -> impl Iterator<Item=SynsetRelationView<'_>> {
+        self.synset_relations.iter().map(|sr| synset_relation_to_view(self, sr))
+    }
+
+    /// Returns the synset relations whose parent is the synset with the specified ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The identifier of the synset the relations originate from.
+    /// * `relations` - The relation type IDs restricting the results, or none for every type.
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields `SynsetRelationView` instances for each outgoing relation.
+    pub fn synset_relations_from(
+        &self,
+        id: usize,
+        relations: impl IntoIterator<Item=usize>,
+    ) -> impl Iterator<Item=SynsetRelationView<'_>> {
+        self.synset_relations_by_index(&self.synset_relations_out, id, EdgeFilter::from_relations(relations))
+    }
+
+    /// Returns the synset relations whose child is the synset with the specified ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The identifier of the synset the relations point to.
+    /// * `relations` - The relation type IDs restricting the results, or none for every type.
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields `SynsetRelationView` instances for each incoming relation.
+    pub fn synset_relations_to(
+        &self,
+        id: usize,
+        relations: impl IntoIterator<Item=usize>,
+    ) -> impl Iterator<Item=SynsetRelationView<'_>> {
+        self.synset_relations_by_index(&self.synset_relations_in, id, EdgeFilter::from_relations(relations))
+    }
+
+    fn synset_relations_by_index<'a>(
+        &'a self,
+        index: &'a HashMap<usize, Vec<usize>>,
+        id: usize,
+        relations: EdgeFilter,
+    ) -> impl Iterator<Item=SynsetRelationView<'a>> {
+        index.get(&id)
+            .into_iter()
+            .flatten()
+            .map(|&i| &self.synset_relations[i])
+            .filter(move |sr| relations.accepts(sr.relation))
+            .map(|sr| synset_relation_to_view(self, sr))
     }
 
     /// Retrieves a relation type with the specified ID from the plWordNet.
@@ -151,8 +515,8 @@ impl PlWordNet {
     ///
     /// An `Option` containing the `RelationTypeView` if a relation type with the given ID is found,
     /// or `None` if no relation type exists with the specified ID.
-    pub fn get_relation_type(&self, id: usize) -> Option<RelationTypeView> {
-        self.relation_types.get(&id).map(|rt| rt.into())
+    pub fn get_relation_type(&self, id: usize) -> Option<RelationTypeView<'_>> {
+        self.relation_types.get(&id).map(|rt| relation_type_to_view(self, rt))
     }
 
     /// Returns an iterator over the relation types in the plWordNet.
@@ -164,8 +528,45 @@ impl PlWordNet {
     ///
     /// An iterator that yields `RelationTypeView` instances representing the relation types in the
     /// plWordNet.
-    pub fn iter_relation_types(&self) -> impl Iterator<Item=RelationTypeView> {
-        self.relation_types.values().map(|rt| rt.into())
+    pub fn iter_relation_types(&self) -> impl Iterator<Item=RelationTypeView<'_>> {
+        self.relation_types.values().map(|rt| relation_type_to_view(self, rt))
+    }
+
+    /// Retrieves the relation type with the specified name, such as `"hiperonimia"`.
+    ///
+    /// Subtypes of different relation types may share a name; in that case the relation type
+    /// with the lowest ID is returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the relation type to retrieve.
+    ///
+    /// # Returns
+    ///
+    /// An `Option` containing the `RelationTypeView` if a relation type with the given name is
+    /// found, or `None` otherwise.
+    pub fn relation_type_by_name(&self, name: &str) -> Option<RelationTypeView<'_>> {
+        self.relation_types.values()
+            .filter(|rt| rt.name == name)
+            .min_by_key(|rt| rt.id)
+            .map(|rt| relation_type_to_view(self, rt))
+    }
+
+    /// Retrieves the relation type with the specified shortcut, such as `"hiper"`.
+    ///
+    /// # Arguments
+    ///
+    /// * `shortcut` - The shortcut of the relation type to retrieve.
+    ///
+    /// # Returns
+    ///
+    /// An `Option` containing the `RelationTypeView` if a relation type with the given shortcut
+    /// is found, or `None` otherwise.
+    pub fn relation_type_by_shortcut(&self, shortcut: &str) -> Option<RelationTypeView<'_>> {
+        self.relation_types.values()
+            .filter(|rt| rt.shortcut == shortcut)
+            .min_by_key(|rt| rt.id)
+            .map(|rt| relation_type_to_view(self, rt))
     }
 }
 
@@ -181,25 +582,97 @@ impl<'a> From<&'a LexicalUnit> for LexicalUnitView<'a> {
             workstate: &lu.workstate,
             source: &lu.source,
             variant: lu.variant,
-            language: if lu.pos.ends_with(" pwn") { Language::EN } else { Language::PL },
+            part_of_speech: lu.part_of_speech,
+            language: lu.language,
+            semantic_domain: lu.semantic_domain,
+            state: &lu.state,
+            synset_id: lu.synset,
         }
     }
 }
 
-impl<'a> From<&'a RelationType> for RelationTypeView<'a> {
-    fn from(rt: &'a RelationType) -> Self {
-        Self {
-            id: rt.id,
-            type_: &rt.type_,
-            reverse: rt.reverse,
-            name: &rt.name,
-            description: &rt.description,
-            posstr: &rt.posstr,
-            display: &rt.display,
-            shortcut: &rt.shortcut,
-            autoreverse: rt.autoreverse,
-            pwn: &rt.pwn,
-        }
+impl Debug for RelationTypeView<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RelationTypeView")
+            .field("id", &self.id)
+            .field("type_", &self.type_)
+            .field("parent", &self.parent)
+            .field("reverse", &self.reverse)
+            .field("name", &self.name)
+            .field("description", &self.description)
+            .field("posstr", &self.posstr)
+            .field("display", &self.display)
+            .field("shortcut", &self.shortcut)
+            .field("autoreverse", &self.autoreverse)
+            .field("pwn", &self.pwn)
+            .field("tests", &self.tests)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a> RelationTypeView<'a> {
+    /// Retrieves the reverse of this relation type, e.g. hypernymy for hyponymy.
+    ///
+    /// # Returns
+    ///
+    /// An `Option` containing the `RelationTypeView` of the reverse relation type, or `None` if
+    /// this relation type has no reverse.
+    pub fn reverse_type(&self) -> Option<RelationTypeView<'a>> {
+        self.wordnet.get_relation_type(self.reverse)
+    }
+
+    /// Retrieves the relation type this one is a subtype of.
+    ///
+    /// # Returns
+    ///
+    /// An `Option` containing the `RelationTypeView` of the parent relation type, or `None` if
+    /// this is a top-level relation type.
+    pub fn parent_type(&self) -> Option<RelationTypeView<'a>> {
+        self.parent.and_then(|id| self.wordnet.get_relation_type(id))
+    }
+
+    /// Returns an iterator over the direct subtypes of this relation type.
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields `RelationTypeView` instances ordered by ID.
+    pub fn sub_types(&self) -> impl Iterator<Item=RelationTypeView<'a>> {
+        let wordnet = self.wordnet;
+        wordnet.relation_subtypes.get(&self.id)
+            .into_iter()
+            .flatten()
+            .filter_map(move |&id| wordnet.get_relation_type(id))
+    }
+
+    /// Returns an iterator over the substitution tests defined for this relation type.
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields `RelationTypeTestView` instances in the order they appear in the
+    /// plWordNet data.
+    pub fn tests(&self) -> impl Iterator<Item=RelationTypeTestView<'a>> {
+        self.tests.iter().map(|test| RelationTypeTestView {
+            text: &test.text,
+            pos: &test.pos,
+        })
+    }
+}
+
+impl RelationTypeTestView<'_> {
+    /// Fills in the test template with two words.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - The word substituted for the `<x#>` placeholder.
+    /// * `y` - The word substituted for the `<y#>` placeholder.
+    ///
+    /// # Returns
+    ///
+    /// The test sentence with both placeholders replaced.
+    pub fn instantiate(&self, x: &str, y: &str) -> String {
+        self.text
+            .replace("<x#>", x)
+            .replace("<y#>", y)
     }
 }
 
@@ -207,7 +680,8 @@ fn synset_to_view<'a>(wn: &'a PlWordNet, s: &'a Synset) -> SynsetView<'a> {
     let lus: Vec<_> = s.lexical_units.iter()
         .filter_map(|&id| wn.get_lexical_unit(id))
         .collect();
-    let language = lus.first().map_or(Language::PL, |lu| lu.language);
+    // a synset without lexical units has no language of its own and is reported as Polish
+    let language = synset_language(wn, s).unwrap_or(Language::PL);
     SynsetView {
         id: s.id,
         workstate: &s.workstate,
@@ -217,6 +691,52 @@ fn synset_to_view<'a>(wn: &'a PlWordNet, s: &'a Synset) -> SynsetView<'a> {
         desc: &s.desc,
         abstract_: s.abstract_,
         lexical_units: lus,
-        language
+        language,
+        state: &s.state,
+    }
+}
+
+/// Language of the first lexical unit of a synset, or `None` if the synset has no lexical units.
+pub(crate) fn synset_language(wn: &PlWordNet, s: &Synset) -> Option<Language> {
+    s.lexical_units.iter()
+        .find_map(|id| wn.lexical_units.get(id))
+        .map(|lu| lu.language)
+}
+
+pub(crate) fn lexical_relation_to_view<'a>(wn: &'a PlWordNet, lr: &'a LexicalRelation) -> LexicalRelationView<'a> {
+    LexicalRelationView {
+        parent: wn.get_lexical_unit(lr.parent),
+        child: wn.get_lexical_unit(lr.child),
+        relation: wn.get_relation_type(lr.relation),
+        valid: lr.valid,
+        owner: &lr.owner,
+    }
+}
+
+pub(crate) fn synset_relation_to_view<'a>(wn: &'a PlWordNet, sr: &'a SynsetRelation) -> SynsetRelationView<'a> {
+    SynsetRelationView {
+        parent: wn.get_synset(sr.parent),
+        child: wn.get_synset(sr.child),
+        relation: wn.get_relation_type(sr.relation),
+        valid: sr.valid,
+        owner: &sr.owner,
+    }
+}
+
+fn relation_type_to_view<'a>(wn: &'a PlWordNet, rt: &'a RelationType) -> RelationTypeView<'a> {
+    RelationTypeView {
+        id: rt.id,
+        type_: &rt.type_,
+        parent: rt.parent,
+        reverse: rt.reverse,
+        name: &rt.name,
+        description: &rt.description,
+        posstr: &rt.posstr,
+        display: &rt.display,
+        shortcut: &rt.shortcut,
+        autoreverse: rt.autoreverse,
+        pwn: &rt.pwn,
+        tests: &rt.tests,
+        wordnet: wn,
     }
 }
@@ -1,8 +1,12 @@
-use std::collections::{HashMap};
+use std::collections::{HashMap, HashSet};
+use std::io;
+use std::sync::OnceLock;
+use crate::emotion::{Emotion, Polarity};
+use crate::search::LemmaTree;
 
 
 /// Represents the language of lexical units and synsets in plWordNet.
-#[derive(Copy, Clone, Eq, PartialEq)]
+#[derive(Copy, Clone, Eq, PartialEq, Hash)]
 pub enum Language {
     /// Indicates Polish language.
     PL,
@@ -10,6 +14,174 @@ pub enum Language {
     EN,
 }
 
+/// Represents the part of speech of a lexical unit in plWordNet.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum PartOfSpeech {
+    /// Indicates a noun (`rzeczownik`).
+    Noun,
+    /// Indicates a verb (`czasownik`).
+    Verb,
+    /// Indicates an adjective (`przymiotnik`).
+    Adjective,
+    /// Indicates an adverb (`przysłówek`).
+    Adverb,
+}
+
+/// Represents the editorial state of a lexical unit or synset in plWordNet.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum Workstate {
+    /// Indicates an entry which has not been processed yet (`Nie przetworzone`).
+    Unprocessed,
+    /// Indicates an entry which has been partially processed (`Częściowo przetworzone`).
+    PartiallyProcessed,
+    /// Indicates an entry which has been verified by a lexicographer (`Sprawdzone`).
+    Verified,
+    /// Indicates an entry which has been marked as erroneous (`Błąd`).
+    Error,
+    /// Indicates a workstate with any other name, including an empty one when the source data
+    /// does not give it.
+    Other(String),
+}
+
+/// Represents a semantic domain of lexical units in plWordNet, identified in the source data by a
+/// short code such as `zw`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Domain {
+    /// Indicates top concepts (`bhp`).
+    Tops,
+    /// Indicates acts and actions (`czy`).
+    Acts,
+    /// Indicates artifacts (`wytw`).
+    Artifacts,
+    /// Indicates attributes of people and animals (`cech`).
+    Attributes,
+    /// Indicates body parts (`czc`).
+    BodyParts,
+    /// Indicates cognition (`umy`).
+    Cognition,
+    /// Indicates communication (`por`).
+    Communication,
+    /// Indicates events (`zdarz`).
+    Events,
+    /// Indicates feelings and emotions (`czuj`).
+    Feelings,
+    /// Indicates food (`jedz`).
+    Food,
+    /// Indicates groups of people and things (`grp`).
+    Groups,
+    /// Indicates places and locations (`msc`).
+    Locations,
+    /// Indicates goals of actions (`cel`).
+    Motives,
+    /// Indicates natural objects (`rz`).
+    Objects,
+    /// Indicates people (`os`).
+    People,
+    /// Indicates natural phenomena (`zj`).
+    Phenomena,
+    /// Indicates plants (`rsl`).
+    Plants,
+    /// Indicates possession and its transfer (`pos`).
+    Possession,
+    /// Indicates natural processes (`prc`).
+    Processes,
+    /// Indicates quantities and units of measure (`il`).
+    Quantities,
+    /// Indicates animals (`zw`).
+    Animals,
+    /// Indicates taxonomy and classification (`sys`).
+    Taxonomy,
+    /// Indicates units not assigned to any particular domain (`wsz`).
+    Unspecified,
+    /// Indicates static situations (states) (`st`).
+    States,
+    /// Indicates substances (`sbst`).
+    Substances,
+    /// Indicates time and temporal relations (`czas`).
+    Time,
+    /// Indicates relations between people, things or ideas (`zwz`).
+    Relations,
+    /// Indicates shapes (`ksz`).
+    Shapes,
+    /// Indicates body care (`hig`).
+    BodyCare,
+    /// Indicates change of size, temperature, intensity and the like (`zmn`).
+    Change,
+    /// Indicates verbs of thinking (`cumy`).
+    VerbsOfThinking,
+    /// Indicates verbs of speaking, singing and the like (`cpor`).
+    VerbsOfSpeaking,
+    /// Indicates verbs of physical competition (`wal`).
+    Competition,
+    /// Indicates verbs of eating (`cjedz`).
+    Consumption,
+    /// Indicates verbs of physical contact (`dtk`).
+    Contact,
+    /// Indicates verbs of creation (`cwytw`).
+    Creation,
+    /// Indicates verbs of feeling (`cczuj`).
+    VerbsOfFeeling,
+    /// Indicates verbs of motion (`ruch`).
+    Motion,
+    /// Indicates verbs of perception (`pst`).
+    Perception,
+    /// Indicates verbs of possession and its transfer (`cpos`).
+    VerbsOfPossession,
+    /// Indicates verbs of social interaction (`sp`).
+    Social,
+    /// Indicates stative verbs (`cst`).
+    Stative,
+    /// Indicates verbs of weather phenomena (`pog`).
+    Weather,
+    /// Indicates qualitative adjectives (`jak`).
+    Qualitative,
+    /// Indicates relational adjectives (`rel`).
+    Relational,
+    /// Indicates deverbal adjectives (`odcz`).
+    Deverbal,
+}
+
+/// Represents an error that can occur while loading plWordNet data.
+#[derive(Debug)]
+pub enum PlWordNetError {
+    /// The underlying source could not be opened or read.
+    Io(io::Error),
+    /// The file is compressed with a format whose cargo feature is not enabled.
+    UnsupportedCompression(&'static str),
+    /// The XML document is malformed.
+    Xml {
+        /// Byte offset in the input at which the error was detected.
+        position: usize,
+        source: quick_xml::Error,
+    },
+    /// An attribute value could not be converted to the expected type.
+    BadAttribute {
+        tag: String,
+        attribute: String,
+        value: String,
+    },
+    /// An element that is not part of the plWordNet schema was encountered.
+    UnknownElement {
+        /// Byte offset in the input at which the element ends.
+        position: usize,
+        tag: String,
+    },
+    /// The document does not contain the `<array-list>` root element.
+    MissingRoot,
+    /// An element was encountered outside of the parent element it belongs to.
+    DanglingContext {
+        /// Byte offset in the input at which the element ends.
+        position: usize,
+        tag: String,
+    },
+    /// A line of a frequency file is not a lemma followed by its count.
+    BadFrequencyLine {
+        /// Line number in the file, starting at `1`.
+        line: usize,
+        content: String,
+    },
+}
+
 /// Represents the plWordNet lexical resource.
 #[derive(Debug)]
 pub struct PlWordNet {
@@ -21,6 +193,35 @@ pub struct PlWordNet {
     pub(crate) relation_types: HashMap<usize, RelationType>,
     pub(crate) lexical_relations: Vec<LexicalRelation>,
     pub(crate) synset_relations: Vec<SynsetRelation>,
+    /// Indices into `sorted_lemmas` keyed by the lowercase form of the lemma without Polish
+    /// diacritics, in ascending order.
+    pub(crate) folded_lemma_index: HashMap<String, Vec<usize>>,
+    /// Distinct lemmas in lexicographic order.
+    pub(crate) sorted_lemmas: Vec<String>,
+    /// Lexical unit ids of each lemma in `sorted_lemmas`, ordered by variant.
+    pub(crate) lemma_index: Vec<Vec<usize>>,
+    /// Edit distance index over `sorted_lemmas`, built on the first fuzzy search.
+    pub(crate) lemma_tree: OnceLock<LemmaTree>,
+    /// Lexical unit ids keyed by their semantic domain, ordered by id.
+    pub(crate) domain_index: HashMap<Domain, Vec<usize>>,
+    /// Indices into `lexical_relations` keyed by the parent lexical unit id.
+    pub(crate) lexical_relations_out: HashMap<usize, Vec<usize>>,
+    /// Indices into `lexical_relations` keyed by the child lexical unit id.
+    pub(crate) lexical_relations_in: HashMap<usize, Vec<usize>>,
+    /// Indices into `synset_relations` keyed by the parent synset id.
+    pub(crate) synset_relations_out: HashMap<usize, Vec<usize>>,
+    /// Indices into `synset_relations` keyed by the child synset id.
+    pub(crate) synset_relations_in: HashMap<usize, Vec<usize>>,
+    /// Relation type ids keyed by the id of their parent relation type, ordered by id.
+    pub(crate) relation_subtypes: HashMap<usize, Vec<usize>>,
+    /// Ids of the hypernymy relation type and its subtypes.
+    pub(crate) hypernymy_types: HashSet<usize>,
+    /// Ids of the hyponymy relation type and its subtypes.
+    pub(crate) hyponymy_types: HashSet<usize>,
+    /// Lexical unit ids keyed by the polarity assigned by any annotator, ordered by id.
+    pub(crate) polarity_index: HashMap<Polarity, Vec<usize>>,
+    /// Lexical unit ids keyed by the emotion assigned by any annotator, ordered by id.
+    pub(crate) emotion_index: HashMap<Emotion, Vec<usize>>,
 }
 
 /// Metadata information for a PlWordNet instance.
@@ -47,7 +248,12 @@ pub(crate) struct LexicalUnit {
     pub workstate: String,
     pub source: String,
     pub variant: i32,
-    pub language: String,
+    pub part_of_speech: Option<PartOfSpeech>,
+    pub language: Language,
+    pub semantic_domain: Option<Domain>,
+    pub state: Workstate,
+    /// Id of the synset containing this lexical unit.
+    pub synset: Option<usize>,
 }
 
 /// Represents a readonly view of a lexical unit.
@@ -55,6 +261,7 @@ pub(crate) struct LexicalUnit {
 pub struct LexicalUnitView<'a> {
     pub id: usize,
     pub name: &'a str,
+    /// Part of speech as written in the source data, e.g. `"czasownik pwn"`.
     pub pos: &'a str,
     pub tagcount: i32,
     pub domain: &'a str,
@@ -62,7 +269,15 @@ pub struct LexicalUnitView<'a> {
     pub workstate: &'a str,
     pub source: &'a str,
     pub variant: i32,
+    /// Part of speech parsed from `pos`, or `None` if it is not recognised.
+    pub part_of_speech: Option<PartOfSpeech>,
     pub language: Language,
+    /// Semantic domain parsed from `domain`, or `None` if it is not recognised.
+    pub semantic_domain: Option<Domain>,
+    /// Workstate parsed from `workstate`.
+    pub state: &'a Workstate,
+    /// Id of the synset containing this lexical unit, if any.
+    pub synset_id: Option<usize>,
 }
 
 #[derive(Debug)]
@@ -75,6 +290,7 @@ pub(crate) struct Synset {
     pub desc: String,
     pub abstract_: bool,
     pub lexical_units: Vec<usize>,
+    pub state: Workstate,
 }
 
 /// Represents a readonly view of a synset.
@@ -89,12 +305,15 @@ pub struct SynsetView<'a> {
     pub abstract_: bool,
     pub lexical_units: Vec<LexicalUnitView<'a>>,
     pub language: Language,
+    /// Workstate parsed from `workstate`.
+    pub state: &'a Workstate,
 }
 
 #[derive(Debug)]
 pub(crate) struct RelationType {
     pub id: usize,
     pub type_: String,
+    pub parent: Option<usize>,
     pub reverse: usize,
     pub name: String,
     pub description: String,
@@ -112,10 +331,13 @@ pub(crate) struct RelationTypeTest {
     pub pos: String,
 }
 
-#[derive(Debug)]
+/// Represents a readonly view of a relation type.
 pub struct RelationTypeView<'a> {
     pub id: usize,
     pub type_: &'a str,
+    /// Id of the relation type this one is a subtype of.
+    pub parent: Option<usize>,
+    /// Id of the reverse relation type, or `0` if there is none.
     pub reverse: usize,
     pub name: &'a str,
     pub description: &'a str,
@@ -124,7 +346,18 @@ pub struct RelationTypeView<'a> {
     pub shortcut: &'a str,
     pub autoreverse: bool,
     pub pwn: &'a str,
-    // TODO: RelationTypeTest
+    pub(crate) tests: &'a [RelationTypeTest],
+    pub(crate) wordnet: &'a PlWordNet,
+}
+
+/// Represents a readonly view of a substitution test of a relation type, such as
+/// `"<x#> jest rodzajem <y#>"`.
+#[derive(Debug, Clone)]
+pub struct RelationTypeTestView<'a> {
+    /// Test template with `<x#>` and `<y#>` placeholders for the related words.
+    pub text: &'a str,
+    /// Part of speech the test applies to.
+    pub pos: &'a str,
 }
 
 #[derive(Debug)]
@@ -1,39 +1,87 @@
 use std::borrow::Cow;
-use std::collections::HashMap;
-use std::error::Error;
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
 
 use quick_xml::events::{BytesStart, Event};
 use quick_xml::Reader;
 use crate::model::*;
+use crate::emotion::build_emotion_indexes;
+use crate::search::{fold_lemma, LemmaMatch};
+use crate::workstate::LoadOptions;
 
 
 fn cast_string(text: Cow<[u8]>) -> String {
     String::from_utf8_lossy(text.as_ref()).to_string()
 }
 
-fn cast_usize(text: Cow<[u8]>) -> usize {
-    cast_string(text).parse().unwrap()
+fn cast_usize(text: Cow<[u8]>) -> Result<usize, String> {
+    let text = cast_string(text);
+    text.parse().map_err(|_| text)
 }
 
-fn cast_i32(text: Cow<[u8]>) -> i32 {
-    cast_string(text).parse().unwrap()
+fn cast_i32(text: Cow<[u8]>) -> Result<i32, String> {
+    let text = cast_string(text);
+    text.parse().map_err(|_| text)
+}
+
+fn cast_id(text: Cow<[u8]>) -> Result<Option<usize>, String> {
+    match cast_string(text) {
+        text if text.is_empty() => Ok(None),
+        text => text.parse().map(Some).map_err(|_| text),
+    }
+}
+
+fn cast_escaped(text: Cow<[u8]>) -> Result<String, String> {
+    let text = cast_string(text);
+    match quick_xml::escape::unescape(&text) {
+        Ok(unescaped) => Ok(unescaped.into_owned()),
+        Err(_) => Err(text),
+    }
 }
 
 fn cast_bool(text: Cow<[u8]>) -> bool {
     cast_string(text).eq("true")
 }
 
+fn parse_pos(pos: &str) -> (Option<PartOfSpeech>, Language) {
+    let (pos, language) = match pos.strip_suffix(" pwn") {
+        Some(pos) => (pos, Language::EN),
+        None => (pos, Language::PL),
+    };
+    let pos = match pos {
+        "rzeczownik" => Some(PartOfSpeech::Noun),
+        "czasownik" => Some(PartOfSpeech::Verb),
+        "przymiotnik" => Some(PartOfSpeech::Adjective),
+        "przysłówek" => Some(PartOfSpeech::Adverb),
+        _ => None,
+    };
+    (pos, language)
+}
+
+fn tag_name(event: &BytesStart) -> String {
+    cast_string(Cow::Borrowed(event.name().as_ref()))
+}
+
 macro_rules! gen_branch_test {
     ($x:ident) => { bstringify::bstringify!($x) };
     ($x:ident, $test:literal) => { $test };
 }
 
 macro_rules! gen_cast {
-    ($expr:expr) => { cast_string($expr) };
+    ($expr:expr) => { Ok::<_, String>(cast_string($expr)) };
     ($expr:expr, usize) => { cast_usize($expr) };
+    ($expr:expr, id) => { cast_id($expr) };
     ($expr:expr, i32) => { cast_i32($expr) };
-    ($expr:expr, bool) => { cast_bool($expr) };
-    ($expr:expr, _) => { cast_string($expr) };
+    ($expr:expr, bool) => { Ok::<_, String>(cast_bool($expr)) };
+    ($expr:expr, escaped) => { cast_escaped($expr) };
+    ($expr:expr, _) => { Ok::<_, String>(cast_string($expr)) };
+}
+
+macro_rules! gen_default {
+    () => { Default::default() };
+    ($default:expr) => { $default };
 }
 
 macro_rules! gen_parser {
@@ -42,22 +90,30 @@ macro_rules! gen_parser {
         $structure:ident,
         $( {$($mapped:ident),*} ,)?
         $( [$($nested:ident),*] ,)?
+        $( ($($defaulted:ident $(= $default:expr)?),*) ,)?
         $($x:ident $(: $test:literal)? $(-> $ty:tt)? ),*
     ) => {
-        fn $name(event: &BytesStart) -> $structure {
+        fn $name(event: &BytesStart, position: usize) -> Result<$structure, PlWordNetError> {
             $(let mut $x = None;)*
             for attr in event.attributes() {
-                let attr = attr.unwrap();
+                let attr = attr.map_err(|e| PlWordNetError::Xml { position, source: e.into() })?;
                 match attr.key.0 {
-                    $(gen_branch_test!($x $(, $test)?) => $x = Some(gen_cast!(attr.value $(, $ty)?)),)*
+                    $(gen_branch_test!($x $(, $test)?) => $x = Some(
+                        gen_cast!(attr.value $(, $ty)?).map_err(|value| PlWordNetError::BadAttribute {
+                            tag: tag_name(event),
+                            attribute: cast_string(Cow::Borrowed(attr.key.0)),
+                            value,
+                        })?
+                    ),)*
                     _ => ()
                 };
             }
-            $structure {
+            Ok($structure {
                 $( $x: $x.unwrap_or(Default::default()), )*
                 $( $( $nested: Vec::new(), )* )?
                 $( $( $mapped: HashMap::new(), )* )?
-            }
+                $( $( $defaulted: gen_default!($($default)?), )* )?
+            })
         }
     }
 }
@@ -65,8 +121,22 @@ macro_rules! gen_parser {
 
 gen_parser!(parse_array_list,
     PlWordNet,
-    {lexical_units, synsets, relation_types},
-    [lexical_relations, synset_relations],
+    {
+        lexical_units,
+        synsets,
+        relation_types,
+        folded_lemma_index,
+        domain_index,
+        lexical_relations_out,
+        lexical_relations_in,
+        synset_relations_out,
+        synset_relations_in,
+        relation_subtypes,
+        polarity_index,
+        emotion_index
+    },
+    [lexical_relations, synset_relations, sorted_lemmas, lemma_index],
+    (hypernymy_types, hyponymy_types, lemma_tree),
     owner,
     date,
     version
@@ -74,6 +144,7 @@ gen_parser!(parse_array_list,
 
 gen_parser!(parse_lexical_unit,
     LexicalUnit,
+    (part_of_speech, language = Language::PL, semantic_domain, state = Workstate::Unprocessed, synset),
     id -> usize,
     name,
     pos,
@@ -82,13 +153,13 @@ gen_parser!(parse_lexical_unit,
     desc,
     workstate,
     source,
-    variant -> i32,
-    language
+    variant -> i32
 );
 
 gen_parser!(parse_synset,
     Synset,
     [lexical_units],
+    (state = Workstate::Unprocessed),
     id -> usize,
     workstate,
     split -> i32,
@@ -103,6 +174,7 @@ gen_parser!(parse_relation_type,
     [tests],
     id -> usize,
     type_: b"type",
+    parent -> id,
     reverse -> usize,
     name,
     description,
@@ -115,7 +187,7 @@ gen_parser!(parse_relation_type,
 
 gen_parser!(parse_relation_type_test,
     RelationTypeTest,
-    text,
+    text -> escaped,
     pos
 );
 
@@ -148,6 +220,15 @@ const TAG_SYNSET_RELATION: &[u8] = b"synsetrelations";
 const TAG_UNIT_ID: &[u8] = b"unit-id";
 
 
+const REL_HYPERNYMY: &str = "hiperonimia";
+const REL_HYPONYMY: &str = "hiponimia";
+
+
+const MAGIC_GZIP: &[u8] = &[0x1f, 0x8b];
+const MAGIC_XZ: &[u8] = &[0xfd, b'7', b'z', b'X', b'Z', 0x00];
+const MAGIC_ZSTD: &[u8] = &[0x28, 0xb5, 0x2f, 0xfd];
+
+
 enum ParsingContext {
     None,
     Synset(usize),
@@ -155,49 +236,51 @@ enum ParsingContext {
 }
 
 
-fn from_file(path: &str) -> Result<PlWordNet, Box<dyn Error>> {
-    let mut reader = Reader::from_file(path)?;
+pub(crate) fn from_reader<R: BufRead>(source: R, options: &LoadOptions) -> Result<PlWordNet, PlWordNetError> {
+    let mut reader = Reader::from_reader(source);
     let mut buf = Vec::new();
 
     let mut root: Option<PlWordNet> = None;
     let mut context = ParsingContext::None;
+    let mut unit_synsets = HashMap::new();
     loop {
-        match reader.read_event_into(&mut buf) {
-            Err(e) => panic!("Error at position {}: {:?}", reader.buffer_position(), e),
+        let event = reader.read_event_into(&mut buf);
+        let position = reader.buffer_position();
+        match event {
+            Err(source) => return Err(PlWordNetError::Xml { position, source }),
             Ok(Event::Eof) => break,
             // <node attribute="" />
             Ok(Event::Empty(e)) => {
                 match e.name().as_ref() {
                     TAG_LEXICAL_UNIT => {
-                        let mut data = parse_lexical_unit(&e);
-                        data.language = match data.pos.ends_with(" pwn") {
-                            true => "en".to_string(),
-                            false => "pl".to_string(),
-                        };
-                        root.as_mut().unwrap().lexical_units.insert(data.id, data);
+                        let mut data = parse_lexical_unit(&e, position)?;
+                        (data.part_of_speech, data.language) = parse_pos(&data.pos);
+                        data.semantic_domain = Domain::from_code(&data.domain);
+                        data.state = Workstate::from_name(&data.workstate);
+                        root_mut(&mut root)?.lexical_units.insert(data.id, data);
                     },
                     TAG_RELATION_TYPE_TEST => {
-                        let data = parse_relation_type_test(&e);
+                        let data = parse_relation_type_test(&e, position)?;
                         match context {
                             ParsingContext::RelationType(id) => {
-                                root.as_mut().unwrap().relation_types
+                                root_mut(&mut root)?.relation_types
                                     .entry(id)
                                     .and_modify(|e| e.tests.push(data));
                             },
-                            _ => unreachable!()
+                            _ => return Err(PlWordNetError::DanglingContext { position, tag: tag_name(&e) }),
                         }
                     },
                     TAG_LEXICAL_RELATION => {
-                        let data = parse_lexical_relation(&e);
-                        root.as_mut().unwrap().lexical_relations.push(data);
+                        let data = parse_lexical_relation(&e, position)?;
+                        root_mut(&mut root)?.lexical_relations.push(data);
                     },
                     TAG_SYNSET_RELATION => {
-                        let data = parse_synset_relation(&e);
-                        root.as_mut().unwrap().synset_relations.push(data)
+                        let data = parse_synset_relation(&e, position)?;
+                        root_mut(&mut root)?.synset_relations.push(data)
                     },
                     TAG_RELATION_TYPE => {
-                        let data = parse_relation_type(&e);
-                        root.as_mut().unwrap().relation_types.insert(data.id.to_owned(), data);
+                        let data = parse_relation_type(&e, position)?;
+                        root_mut(&mut root)?.relation_types.insert(data.id.to_owned(), data);
                     },
                     _ => (),
                 }
@@ -205,56 +288,181 @@ fn from_file(path: &str) -> Result<PlWordNet, Box<dyn Error>> {
             // <node attribute="">
             Ok(Event::Start(e)) => {
                 match e.name().as_ref() {
-                    TAG_ARRAY_LIST => root = Some(parse_array_list(&e)),
+                    TAG_ARRAY_LIST => root = Some(parse_array_list(&e, position)?),
                     TAG_SYNSET => {
-                        let data = parse_synset(&e);
+                        let mut data = parse_synset(&e, position)?;
+                        data.state = Workstate::from_name(&data.workstate);
                         context = ParsingContext::Synset(data.id);
-                        root.as_mut().unwrap().synsets.insert(data.id.to_owned(), data);
+                        root_mut(&mut root)?.synsets.insert(data.id.to_owned(), data);
                     },
                     TAG_RELATION_TYPE => {
-                        let data = parse_relation_type(&e);
+                        let data = parse_relation_type(&e, position)?;
                         context = ParsingContext::RelationType(data.id);
-                        root.as_mut().unwrap().relation_types.insert(data.id.to_owned(), data);
+                        root_mut(&mut root)?.relation_types.insert(data.id.to_owned(), data);
                     },
-                    TAG_UNIT_ID => (),
-                    _ => unreachable!(),
+                    TAG_UNIT_ID => match context {
+                        ParsingContext::Synset(_) => (),
+                        _ => return Err(PlWordNetError::DanglingContext { position, tag: tag_name(&e) }),
+                    },
+                    _ => return Err(PlWordNetError::UnknownElement { position, tag: tag_name(&e) }),
+                }
+            }
+            // </node>
+            Ok(Event::End(e)) => {
+                match e.name().as_ref() {
+                    TAG_SYNSET | TAG_RELATION_TYPE => context = ParsingContext::None,
+                    _ => (),
                 }
             }
             // Text
-            Ok(Event::Text(event)) => match context {
-                ParsingContext::Synset(id) => {
-                    let text = event.unescape().unwrap().into_owned();
+            Ok(Event::Text(event)) => {
+                if let ParsingContext::Synset(id) = context {
+                    let text = event.unescape()
+                        .map_err(|source| PlWordNetError::Xml { position, source })?;
                     let lu_id = match text.trim() {
                         "" => continue,
-                        num => num.parse().unwrap()
+                        num => num.parse().map_err(|_| PlWordNetError::BadAttribute {
+                            tag: cast_string(Cow::Borrowed(TAG_UNIT_ID)),
+                            attribute: "#text".to_string(),
+                            value: num.to_string(),
+                        })?
                     };
-                    root.as_mut().unwrap().synsets
+                    root_mut(&mut root)?.synsets
                         .entry(id)
                         .and_modify(|e| e.lexical_units.push(lu_id));
-                },
-                _ => (),
+                    unit_synsets.insert(lu_id, id);
+                }
             }
             _ => (),
         }
+        buf.clear();
     }
-    Ok(root.unwrap())
+    let mut root = root.ok_or(PlWordNetError::MissingRoot)?;
+    options.retain(&mut root);
+    build_indexes(&mut root, unit_synsets);
+    Ok(root)
+}
+
+fn build_indexes(wn: &mut PlWordNet, unit_synsets: HashMap<usize, usize>) {
+    for (lu_id, synset_id) in unit_synsets {
+        if !wn.synsets.contains_key(&synset_id) {
+            continue;
+        }
+        if let Some(lu) = wn.lexical_units.get_mut(&lu_id) {
+            lu.synset = Some(synset_id);
+        }
+    }
+    let mut lemmas: Vec<_> = wn.lexical_units.values().map(|lu| lu.name.as_str()).collect();
+    lemmas.sort();
+    lemmas.dedup();
+    wn.sorted_lemmas = lemmas.into_iter().map(str::to_owned).collect();
+    wn.lemma_index = vec![Vec::new(); wn.sorted_lemmas.len()];
+    for lu in wn.lexical_units.values() {
+        if let Ok(i) = wn.sorted_lemmas.binary_search(&lu.name) {
+            wn.lemma_index[i].push(lu.id);
+        }
+    }
+    for ids in wn.lemma_index.iter_mut() {
+        ids.sort_by_key(|id| (wn.lexical_units[id].variant, *id));
+    }
+    for (i, lemma) in wn.sorted_lemmas.iter().enumerate() {
+        let key = fold_lemma(lemma, LemmaMatch::IgnoreCaseAndDiacritics);
+        wn.folded_lemma_index.entry(key).or_default().push(i);
+    }
+    for lu in wn.lexical_units.values() {
+        if let Some(domain) = lu.semantic_domain {
+            wn.domain_index.entry(domain).or_default().push(lu.id);
+        }
+    }
+    for ids in wn.domain_index.values_mut() {
+        ids.sort();
+    }
+    for rt in wn.relation_types.values() {
+        if let Some(parent) = rt.parent {
+            wn.relation_subtypes.entry(parent).or_default().push(rt.id);
+        }
+    }
+    for ids in wn.relation_subtypes.values_mut() {
+        ids.sort();
+    }
+    wn.hypernymy_types = relation_type_family(wn, REL_HYPERNYMY);
+    wn.hyponymy_types = relation_type_family(wn, REL_HYPONYMY);
+    for (index, lr) in wn.lexical_relations.iter().enumerate() {
+        wn.lexical_relations_out.entry(lr.parent).or_default().push(index);
+        wn.lexical_relations_in.entry(lr.child).or_default().push(index);
+    }
+    for (index, sr) in wn.synset_relations.iter().enumerate() {
+        wn.synset_relations_out.entry(sr.parent).or_default().push(index);
+        wn.synset_relations_in.entry(sr.child).or_default().push(index);
+    }
+    build_emotion_indexes(wn);
+}
+
+/// Collects the ids of all relation types with the given name together with their subtypes.
+pub(crate) fn relation_type_family(wn: &PlWordNet, name: &str) -> HashSet<usize> {
+    let mut family = HashSet::new();
+    let mut stack: Vec<_> = wn.relation_types.values()
+        .filter(|rt| rt.name == name)
+        .map(|rt| rt.id)
+        .collect();
+    while let Some(id) = stack.pop() {
+        if family.insert(id) {
+            stack.extend(wn.relation_subtypes.get(&id).into_iter().flatten());
+        }
+    }
+    family
+}
+
+pub(crate) fn from_file(path: &Path, options: &LoadOptions) -> Result<PlWordNet, PlWordNetError> {
+    let file = File::open(path).map_err(PlWordNetError::Io)?;
+    let mut reader = BufReader::new(file);
+    let magic = reader.fill_buf().map_err(PlWordNetError::Io)?;
+
+    if magic.starts_with(MAGIC_GZIP) {
+        #[cfg(feature = "gzip")]
+        return from_reader(BufReader::new(flate2::bufread::MultiGzDecoder::new(reader)), options);
+        #[cfg(not(feature = "gzip"))]
+        return Err(PlWordNetError::UnsupportedCompression("gzip"));
+    }
+    if magic.starts_with(MAGIC_XZ) {
+        #[cfg(feature = "xz")]
+        return from_reader(BufReader::new(xz2::bufread::XzDecoder::new_multi_decoder(reader)), options);
+        #[cfg(not(feature = "xz"))]
+        return Err(PlWordNetError::UnsupportedCompression("xz"));
+    }
+    if magic.starts_with(MAGIC_ZSTD) {
+        #[cfg(feature = "zstd")]
+        return from_reader(BufReader::new(
+            zstd::stream::read::Decoder::with_buffer(reader).map_err(PlWordNetError::Io)?
+        ), options);
+        #[cfg(not(feature = "zstd"))]
+        return Err(PlWordNetError::UnsupportedCompression("zstd"));
+    }
+    from_reader(reader, options)
+}
+
+fn root_mut(root: &mut Option<PlWordNet>) -> Result<&mut PlWordNet, PlWordNetError> {
+    root.as_mut().ok_or(PlWordNetError::MissingRoot)
 }
 
 
 impl PlWordNet {
-    /// Creates a new instance of `PlWordnet` by loading the plWordNet XML file from the specified path.
+    /// Creates a new instance of `PlWordNet` by loading the plWordNet XML file from the specified path.
+    ///
+    /// Files compressed with gzip, xz or zstd are detected by their magic bytes and decompressed
+    /// on the fly when the matching `gzip`, `xz` or `zstd` cargo feature is enabled.
     ///
     /// # Arguments
     ///
-    /// * `path` - A string slice representing the path to the plWordNet XML file.
+    /// * `path` - The path to the plWordNet XML file.
     ///
     /// # Returns
     ///
-    /// * `Result<PlWordnet, Box<dyn Error>>` - A result that contains the `PlWordnet` instance if the file is successfully loaded, or an error if loading fails.
+    /// * `Result<PlWordNet, PlWordNetError>` - A result that contains the `PlWordNet` instance if the file is successfully loaded, or an error describing why loading failed.
     ///
     /// # Example
     ///
-    /// ```rust
+    /// ```rust,no_run
     /// use std::error::Error;
     /// use plwordnet::{PlWordNet};
     ///
@@ -267,7 +475,44 @@ impl PlWordNet {
     ///     Ok(())
     /// }
     /// ```
-    pub fn from_file(path: &str) -> Result<Self, Box<dyn Error>> {
-        from_file(path)
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, PlWordNetError> {
+        from_file(path.as_ref(), &LoadOptions::default())
+    }
+
+    /// Creates a new instance of `PlWordNet` by reading plWordNet XML from any buffered reader,
+    /// such as standard input or a decompressing stream.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - A buffered reader yielding the plWordNet XML document.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<PlWordNet, PlWordNetError>` - A result that contains the `PlWordNet` instance if the document is successfully loaded, or an error describing why loading failed.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use std::io;
+    /// use plwordnet::{PlWordNet};
+    ///
+    /// let plwordnet = PlWordNet::from_reader(io::stdin().lock()).unwrap();
+    /// ```
+    pub fn from_reader<R: BufRead>(reader: R) -> Result<Self, PlWordNetError> {
+        from_reader(reader, &LoadOptions::default())
+    }
+
+    /// Creates a new instance of `PlWordNet` from plWordNet XML held in memory, for example data
+    /// embedded with `include_bytes!`.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The contents of a plWordNet XML document.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<PlWordNet, PlWordNetError>` - A result that contains the `PlWordNet` instance if the document is successfully loaded, or an error describing why loading failed.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, PlWordNetError> {
+        from_reader(bytes, &LoadOptions::default())
     }
 }
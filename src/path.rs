@@ -0,0 +1,209 @@
+use std::collections::{HashMap, VecDeque};
+use crate::logic::{lexical_relation_to_view, synset_relation_to_view};
+use crate::model::{LexicalRelationView, PlWordNet, SynsetRelationView};
+use crate::traversal::EdgeFilter;
+
+
+/// Represents a single relation on a path between two lexical units.
+#[derive(Debug)]
+pub enum RelationStep<'a> {
+    /// A lexical relation between two lexical units.
+    Lexical(LexicalRelationView<'a>),
+    /// A synset relation between the synsets of two lexical units.
+    Synset(SynsetRelationView<'a>),
+}
+
+/// Index of a relation in either `lexical_relations` or `synset_relations`.
+#[derive(Copy, Clone)]
+enum Edge {
+    Lexical(usize),
+    Synset(usize),
+}
+
+/// Node of a breadth-first search: its distance from the start and the node and relation
+/// it was reached through.
+type Visit<E> = (usize, Option<(usize, E)>);
+
+impl PlWordNet {
+    /// Finds the shortest chain of synset relations connecting two synsets.
+    ///
+    /// Relations are followed regardless of their direction, so each returned relation may point
+    /// either towards or away from `b`. The search runs breadth-first from both ends at once.
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - The identifier of the synset the path starts at.
+    /// * `b` - The identifier of the synset the path ends at.
+    /// * `relations` - The relation type IDs the path may use, or none for every type.
+    ///
+    /// # Returns
+    ///
+    /// An `Option` containing the `SynsetRelationView` instances along the path, empty if `a`
+    /// and `b` are the same synset, or `None` if either synset does not exist or they are not
+    /// connected.
+    pub fn shortest_path(
+        &self,
+        a: usize,
+        b: usize,
+        relations: impl IntoIterator<Item=usize>,
+    ) -> Option<Vec<SynsetRelationView<'_>>> {
+        let relations = EdgeFilter::from_relations(relations);
+        if !self.synsets.contains_key(&a) || !self.synsets.contains_key(&b) {
+            return None;
+        }
+        let mut forward: HashMap<usize, Visit<usize>> = HashMap::from([(a, (0, None))]);
+        let mut backward: HashMap<usize, Visit<usize>> = HashMap::from([(b, (0, None))]);
+        let mut forward_frontier = vec![a];
+        let mut backward_frontier = vec![b];
+
+        let meeting = loop {
+            if forward.contains_key(&b) {
+                break b;
+            }
+            if forward_frontier.is_empty() || backward_frontier.is_empty() {
+                return None;
+            }
+            let (frontier, visited, other) = match forward_frontier.len() <= backward_frontier.len() {
+                true => (&mut forward_frontier, &mut forward, &backward),
+                false => (&mut backward_frontier, &mut backward, &forward),
+            };
+            let mut next = Vec::new();
+            let mut meetings = Vec::new();
+            for &id in frontier.iter() {
+                let depth = visited[&id].0 + 1;
+                for (neighbour, edge) in self.synset_neighbours(id, &relations) {
+                    if visited.contains_key(&neighbour) {
+                        continue;
+                    }
+                    visited.insert(neighbour, (depth, Some((id, edge))));
+                    next.push(neighbour);
+                    if let Some((other_depth, _)) = other.get(&neighbour) {
+                        meetings.push((depth + other_depth, neighbour));
+                    }
+                }
+            }
+            if let Some(&(_, meeting)) = meetings.iter().min() {
+                break meeting;
+            }
+            *frontier = next;
+        };
+
+        let mut path = Vec::new();
+        let mut id = meeting;
+        while let Some((_, Some((previous, edge)))) = forward.get(&id) {
+            path.push(*edge);
+            id = *previous;
+        }
+        path.reverse();
+        let mut id = meeting;
+        while let Some((_, Some((next, edge)))) = backward.get(&id) {
+            path.push(*edge);
+            id = *next;
+        }
+        Some(path.into_iter()
+            .map(|i| synset_relation_to_view(self, &self.synset_relations[i]))
+            .collect())
+    }
+
+    /// Finds the shortest chain of relations connecting two lexical units, mixing lexical
+    /// relations between the units with synset relations between their synsets.
+    ///
+    /// Relations are followed regardless of their direction. Lexical units belonging to the same
+    /// synset are treated as interchangeable, so moving between synonyms does not add a step.
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - The identifier of the lexical unit the path starts at.
+    /// * `b` - The identifier of the lexical unit the path ends at.
+    /// * `relations` - The lexical and synset relation type IDs the path may use, or none for every
+    ///   type.
+    ///
+    /// # Returns
+    ///
+    /// An `Option` containing the `RelationStep` instances along the path, empty if `a` and `b`
+    /// are synonyms, or `None` if either lexical unit does not exist or they are not connected.
+    pub fn shortest_lexical_path(
+        &self,
+        a: usize,
+        b: usize,
+        relations: impl IntoIterator<Item=usize>,
+    ) -> Option<Vec<RelationStep<'_>>> {
+        let relations = EdgeFilter::from_relations(relations);
+        if !self.lexical_units.contains_key(&a) || !self.lexical_units.contains_key(&b) {
+            return None;
+        }
+        // 0-1 breadth-first search, moves between synonyms cost nothing
+        let mut visited: HashMap<usize, Visit<Option<Edge>>> = HashMap::from([(a, (0, None))]);
+        let mut queue = VecDeque::from([(0, a)]);
+        while let Some((distance, id)) = queue.pop_front() {
+            if distance > visited[&id].0 {
+                continue;
+            }
+            if id == b {
+                break;
+            }
+            for (neighbour, edge) in self.lexical_unit_neighbours(id, &relations) {
+                let cost = match edge {
+                    Some(_) => distance + 1,
+                    None => distance,
+                };
+                if visited.get(&neighbour).is_some_and(|&(known, _)| known <= cost) {
+                    continue;
+                }
+                visited.insert(neighbour, (cost, Some((id, edge))));
+                match edge {
+                    Some(_) => queue.push_back((cost, neighbour)),
+                    None => queue.push_front((cost, neighbour)),
+                }
+            }
+        }
+
+        visited.get(&b)?;
+        let mut path = Vec::new();
+        let mut id = b;
+        while let Some((_, Some((previous, edge)))) = visited.get(&id) {
+            path.extend(edge.map(|edge| match edge {
+                Edge::Lexical(i) => RelationStep::Lexical(lexical_relation_to_view(self, &self.lexical_relations[i])),
+                Edge::Synset(i) => RelationStep::Synset(synset_relation_to_view(self, &self.synset_relations[i])),
+            }));
+            id = *previous;
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Synsets connected to a synset by a relation in either direction, with the index of that
+    /// relation.
+    fn synset_neighbours(&self, synset_id: usize, relations: &EdgeFilter) -> Vec<(usize, usize)> {
+        let accepts = |relation: usize| relations.accepts(relation);
+        self.synset_edges(synset_id, accepts, accepts).collect()
+    }
+
+    /// Lexical units reachable from a lexical unit in one step: its synonyms with no relation,
+    /// and units connected by a lexical relation or a relation between their synsets.
+    fn lexical_unit_neighbours(&self, lu_id: usize, relations: &EdgeFilter) -> Vec<(usize, Option<Edge>)> {
+        let mut neighbours = Vec::new();
+        let synset = self.lexical_units[&lu_id].synset.and_then(|id| self.synsets.get(&id));
+        if let Some(synset) = synset {
+            neighbours.extend(synset.lexical_units.iter().map(|&id| (id, None)));
+        }
+        neighbours.extend(self.lexical_relations_out.get(&lu_id)
+            .into_iter()
+            .flatten()
+            .filter(|&&i| relations.accepts(self.lexical_relations[i].relation))
+            .map(|&i| (self.lexical_relations[i].child, Some(Edge::Lexical(i)))));
+        neighbours.extend(self.lexical_relations_in.get(&lu_id)
+            .into_iter()
+            .flatten()
+            .filter(|&&i| relations.accepts(self.lexical_relations[i].relation))
+            .map(|&i| (self.lexical_relations[i].parent, Some(Edge::Lexical(i)))));
+        if let Some(synset) = synset {
+            for (other, i) in self.synset_neighbours(synset.id, relations) {
+                let units = self.synsets.get(&other).map_or(&[][..], |s| &s.lexical_units);
+                neighbours.extend(units.iter().map(|&id| (id, Some(Edge::Synset(i)))));
+            }
+        }
+        neighbours.retain(|&(id, _)| id != lu_id && self.lexical_units.contains_key(&id));
+        neighbours
+    }
+}
@@ -0,0 +1,362 @@
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use regex::Regex;
+use crate::model::{Domain, Language, LexicalUnit, LexicalUnitView, PartOfSpeech, PlWordNet, SynsetView};
+use crate::parser::relation_type_family;
+use crate::search::glob_to_regex;
+use crate::traversal::Direction;
+
+
+/// Represents a query over lexical units, parsed from a space-separated list of `key:value`
+/// terms which all have to match.
+///
+/// The following terms are supported:
+///
+/// * `pos:noun`, `pos:verb`, `pos:adj`, `pos:adv` - part of speech, Polish names such as
+///   `pos:rzeczownik` are accepted as well.
+/// * `lang:pl`, `lang:en` - language.
+/// * `domain:zw` - domain code.
+/// * `lemma:zamek` - written form, where `*` stands for any sequence of characters and `?` for
+///   any single character, e.g. `lemma:zam*`.
+/// * `synset:1234` - membership in the synset with the given id.
+/// * `lu:1234` - the lexical unit with the given id.
+/// * `rel:hiperonimia>synset:1234` - a synset relation of the given type, or one of its
+///   subtypes, from the synset of the lexical unit to the given synset. `<` follows the relation
+///   in the opposite direction, and `lu:1234` in place of `synset:1234` follows lexical relations
+///   to the given lexical unit. The relation type may also be given by its id.
+///
+/// Values containing spaces can be enclosed in double quotes, e.g. `lemma:"dom kultury"`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use plwordnet::{PlWordNet, Query};
+///
+/// let plwordnet = PlWordNet::from_file("plwordnet_4_2.xml").unwrap();
+/// let query = Query::parse("pos:noun domain:zw lemma:zam*").unwrap();
+/// for lu in plwordnet.query_lexical_units(&query) {
+///     println!("{} {}", lu.name, lu.variant);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Query {
+    terms: Vec<Term>,
+}
+
+/// Represents an error that can occur while parsing a query.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum QueryError {
+    /// Indicates a term which is not of the form `key:value`.
+    MalformedTerm(String),
+    /// Indicates a term with a key which is not supported.
+    UnknownKey(String),
+    /// Indicates a term with a value which is not valid for its key.
+    BadValue { key: String, value: String },
+    /// Indicates a double quote which is never closed.
+    UnterminatedQuote,
+}
+
+#[derive(Debug, Clone)]
+enum Term {
+    PartOfSpeech(PartOfSpeech),
+    Language(Language),
+    Domain(String),
+    Lemma(LemmaPattern),
+    Synset(usize),
+    LexicalUnit(usize),
+    Relation { relation: String, direction: Direction, target: Target },
+}
+
+#[derive(Debug, Clone)]
+enum LemmaPattern {
+    Exact(String),
+    Prefix(String),
+    Glob(Regex),
+}
+
+#[derive(Debug, Copy, Clone)]
+enum Target {
+    Synset(usize),
+    LexicalUnit(usize),
+}
+
+impl Query {
+    /// Parses a query from its textual form.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The space-separated terms of the query. An empty query matches everything.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the parsed `Query`, or a `QueryError` describing the first term
+    /// which could not be parsed.
+    pub fn parse(query: &str) -> Result<Self, QueryError> {
+        let terms = tokenize(query)?
+            .iter()
+            .map(|token| parse_term(token))
+            .collect::<Result<_, _>>()?;
+        Ok(Query { terms })
+    }
+
+    /// Builds a query from terms which are already split, for example the arguments of a
+    /// command line program. Each term is taken as a whole, so values may contain spaces
+    /// without being quoted.
+    ///
+    /// # Arguments
+    ///
+    /// * `terms` - The `key:value` terms of the query. No terms match everything.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `Query`, or a `QueryError` describing the first term which could
+    /// not be parsed.
+    pub fn from_terms<T: AsRef<str>>(terms: impl IntoIterator<Item=T>) -> Result<Self, QueryError> {
+        let terms = terms.into_iter()
+            .map(|term| parse_term(term.as_ref()))
+            .collect::<Result<_, _>>()?;
+        Ok(Query { terms })
+    }
+}
+
+impl FromStr for Query {
+    type Err = QueryError;
+
+    fn from_str(query: &str) -> Result<Self, Self::Err> {
+        Query::parse(query)
+    }
+}
+
+impl Display for QueryError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            QueryError::MalformedTerm(term) => write!(f, "Malformed query term '{}', expected 'key:value'", term),
+            QueryError::UnknownKey(key) => write!(f, "Unknown query key '{}'", key),
+            QueryError::BadValue { key, value } => write!(f, "Bad value '{}' for query key '{}'", value, key),
+            QueryError::UnterminatedQuote => write!(f, "Unterminated quote in query"),
+        }
+    }
+}
+
+impl Error for QueryError {}
+
+impl PlWordNet {
+    /// Retrieves the lexical units matching a query.
+    ///
+    /// Terms backed by an index, such as `lemma`, `domain`, `synset` and `rel`, are used to select
+    /// candidates, starting from the most selective one, before the remaining terms are checked
+    /// against each candidate. Queries without such terms scan every lexical unit.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query the lexical units have to match.
+    ///
+    /// # Returns
+    ///
+    /// A vector of `LexicalUnitView` instances ordered by id.
+    pub fn query_lexical_units(&self, query: &Query) -> Vec<LexicalUnitView<'_>> {
+        let mut candidates: Vec<HashSet<usize>> = query.terms
+            .iter()
+            .filter_map(|term| self.term_candidates(term))
+            .collect();
+        candidates.sort_by_key(HashSet::len);
+        let mut ids: Vec<usize> = match candidates.split_first() {
+            Some((seed, rest)) => seed.iter()
+                .filter(|id| rest.iter().all(|candidates| candidates.contains(id)))
+                .copied()
+                .collect(),
+            None => self.lexical_units.keys().copied().collect(),
+        };
+        ids.retain(|id| self.lexical_units.get(id).is_some_and(|lu| {
+            query.terms.iter().all(|term| term_accepts(term, lu))
+        }));
+        ids.sort();
+        ids.into_iter()
+            .filter_map(|id| self.get_lexical_unit(id))
+            .collect()
+    }
+
+    /// Retrieves the synsets containing a lexical unit matching a query.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query at least one lexical unit of each synset has to match.
+    ///
+    /// # Returns
+    ///
+    /// A vector of `SynsetView` instances ordered by id.
+    pub fn query_synsets(&self, query: &Query) -> Vec<SynsetView<'_>> {
+        let mut ids: Vec<_> = self.query_lexical_units(query)
+            .into_iter()
+            .filter_map(|lu| lu.synset_id)
+            .collect();
+        ids.sort();
+        ids.dedup();
+        ids.into_iter()
+            .filter_map(|id| self.get_synset(id))
+            .collect()
+    }
+
+    /// Ids of the lexical units a term can match, if the term is backed by an index.
+    fn term_candidates(&self, term: &Term) -> Option<HashSet<usize>> {
+        let candidates = match term {
+            Term::Lemma(LemmaPattern::Exact(lemma)) => self.lemma_unit_ids(lemma)
+                .iter()
+                .copied()
+                .collect(),
+            Term::Lemma(LemmaPattern::Prefix(prefix)) => {
+                let start = self.sorted_lemmas.partition_point(|lemma| lemma < prefix);
+                self.sorted_lemmas[start..]
+                    .iter()
+                    .zip(&self.lemma_index[start..])
+                    .take_while(|(lemma, _)| lemma.starts_with(prefix.as_str()))
+                    .flat_map(|(_, ids)| ids)
+                    .copied()
+                    .collect()
+            },
+            Term::Lemma(LemmaPattern::Glob(pattern)) => self.sorted_lemmas
+                .iter()
+                .zip(&self.lemma_index)
+                .filter(|(lemma, _)| pattern.is_match(lemma))
+                .flat_map(|(_, ids)| ids)
+                .copied()
+                .collect(),
+            Term::Synset(id) => self.synsets.get(id)
+                .map(|synset| synset.lexical_units.iter().copied().collect())
+                .unwrap_or_default(),
+            Term::Domain(code) => self.domain_index.get(&Domain::from_code(code)?)
+                .into_iter()
+                .flatten()
+                .copied()
+                .collect(),
+            Term::LexicalUnit(id) => HashSet::from([*id]),
+            Term::Relation { relation, direction, target } => self.related_lexical_units(relation, *direction, *target),
+            Term::PartOfSpeech(_) | Term::Language(_) => return None,
+        };
+        Some(candidates)
+    }
+
+    /// Ids of the lexical units with a relation of the given type leading to or from `target`.
+    fn related_lexical_units(&self, relation: &str, direction: Direction, target: Target) -> HashSet<usize> {
+        let types = match relation.parse() {
+            Ok(id) => HashSet::from([id]),
+            Err(_) => relation_type_family(self, relation),
+        };
+        // relations leading to the target from a candidate are incoming from the target's side
+        let index = |incoming, outgoing| match direction {
+            Direction::Incoming => outgoing,
+            _ => incoming,
+        };
+        match target {
+            Target::Synset(id) => index(&self.synset_relations_in, &self.synset_relations_out)
+                .get(&id)
+                .into_iter()
+                .flatten()
+                .map(|&i| &self.synset_relations[i])
+                .filter(|sr| types.contains(&sr.relation))
+                .map(|sr| if sr.child == id { sr.parent } else { sr.child })
+                .filter_map(|synset| self.synsets.get(&synset))
+                .flat_map(|synset| synset.lexical_units.iter().copied())
+                .collect(),
+            Target::LexicalUnit(id) => index(&self.lexical_relations_in, &self.lexical_relations_out)
+                .get(&id)
+                .into_iter()
+                .flatten()
+                .map(|&i| &self.lexical_relations[i])
+                .filter(|lr| types.contains(&lr.relation))
+                .map(|lr| if lr.child == id { lr.parent } else { lr.child })
+                .collect(),
+        }
+    }
+}
+
+/// Checks the terms that are not backed by an index against a lexical unit.
+fn term_accepts(term: &Term, lu: &LexicalUnit) -> bool {
+    match term {
+        Term::PartOfSpeech(pos) => lu.part_of_speech == Some(*pos),
+        Term::Language(language) => lu.language == *language,
+        Term::Domain(domain) => lu.domain == *domain,
+        _ => true,
+    }
+}
+
+/// Splits a query into terms at whitespace outside of double quotes, removing the quotes.
+fn tokenize(query: &str) -> Result<Vec<String>, QueryError> {
+    let mut tokens = Vec::new();
+    let mut token = String::new();
+    let mut quoted = false;
+    for c in query.chars() {
+        match c {
+            '"' => quoted = !quoted,
+            c if c.is_whitespace() && !quoted => {
+                if !token.is_empty() {
+                    tokens.push(std::mem::take(&mut token));
+                }
+            },
+            c => token.push(c),
+        }
+    }
+    if quoted {
+        return Err(QueryError::UnterminatedQuote);
+    }
+    if !token.is_empty() {
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+fn parse_term(token: &str) -> Result<Term, QueryError> {
+    let (key, value) = token.split_once(':')
+        .filter(|(key, value)| !key.is_empty() && !value.is_empty())
+        .ok_or_else(|| QueryError::MalformedTerm(token.to_owned()))?;
+    let bad_value = || QueryError::BadValue { key: key.to_owned(), value: value.to_owned() };
+    let term = match key {
+        "pos" => Term::PartOfSpeech(match value {
+            "noun" | "n" | "rzeczownik" => PartOfSpeech::Noun,
+            "verb" | "v" | "czasownik" => PartOfSpeech::Verb,
+            "adj" | "adjective" | "przymiotnik" => PartOfSpeech::Adjective,
+            "adv" | "adverb" | "przysłówek" => PartOfSpeech::Adverb,
+            _ => return Err(bad_value()),
+        }),
+        "lang" => Term::Language(match value {
+            "pl" => Language::PL,
+            "en" => Language::EN,
+            _ => return Err(bad_value()),
+        }),
+        "domain" => Term::Domain(value.to_owned()),
+        "lemma" => Term::Lemma(parse_lemma_pattern(value).ok_or_else(bad_value)?),
+        "synset" => Term::Synset(value.parse().map_err(|_| bad_value())?),
+        "lu" => Term::LexicalUnit(value.parse().map_err(|_| bad_value())?),
+        "rel" => {
+            let split = value.find(['>', '<']).ok_or_else(bad_value)?;
+            let direction = match &value[split..split + 1] {
+                ">" => Direction::Outgoing,
+                _ => Direction::Incoming,
+            };
+            let target = match value[split + 1..].split_once(':') {
+                Some(("synset", id)) => Target::Synset(id.parse().map_err(|_| bad_value())?),
+                Some(("lu", id)) => Target::LexicalUnit(id.parse().map_err(|_| bad_value())?),
+                _ => return Err(bad_value()),
+            };
+            match &value[..split] {
+                "" => return Err(bad_value()),
+                relation => Term::Relation { relation: relation.to_owned(), direction, target },
+            }
+        },
+        _ => return Err(QueryError::UnknownKey(key.to_owned())),
+    };
+    Ok(term)
+}
+
+fn parse_lemma_pattern(value: &str) -> Option<LemmaPattern> {
+    let wildcard = |c| c == '*' || c == '?';
+    match value.find(wildcard) {
+        None => Some(LemmaPattern::Exact(value.to_owned())),
+        Some(index) if index == value.len() - 1 && value.ends_with('*') => {
+            Some(LemmaPattern::Prefix(value[..index].to_owned()))
+        },
+        Some(_) => glob_to_regex(value).ok().map(LemmaPattern::Glob),
+    }
+}
@@ -0,0 +1,369 @@
+use std::collections::HashMap;
+use regex::Regex;
+use crate::model::{LexicalUnitView, PlWordNet, SynsetView};
+
+
+/// Represents how lemmas are compared when searching for lexical units.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum LemmaMatch {
+    /// Matches lemmas written exactly as the query.
+    #[default]
+    Exact,
+    /// Matches lemmas regardless of letter case, e.g. `warszawa` finds `Warszawa`.
+    IgnoreCase,
+    /// Matches lemmas regardless of Polish diacritics, e.g. `zolw` finds `żółw`.
+    IgnoreDiacritics,
+    /// Matches lemmas regardless of both letter case and Polish diacritics.
+    IgnoreCaseAndDiacritics,
+}
+
+/// Represents the text field a pattern search is run against.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum SearchField {
+    /// The written form of a lexical unit.
+    LexicalUnitName,
+    /// The description of a lexical unit.
+    LexicalUnitDesc,
+    /// The definition of a synset.
+    SynsetDefinition,
+    /// The description of a synset.
+    SynsetDesc,
+}
+
+/// Represents an entry matched by a pattern search, depending on the searched `SearchField`.
+#[derive(Debug)]
+pub enum SearchMatch<'a> {
+    LexicalUnit(LexicalUnitView<'a>),
+    Synset(SynsetView<'a>),
+}
+
+impl LemmaMatch {
+    fn ignores_case(self) -> bool {
+        matches!(self, LemmaMatch::IgnoreCase | LemmaMatch::IgnoreCaseAndDiacritics)
+    }
+
+    fn ignores_diacritics(self) -> bool {
+        matches!(self, LemmaMatch::IgnoreDiacritics | LemmaMatch::IgnoreCaseAndDiacritics)
+    }
+}
+
+impl PlWordNet {
+    /// Searches for lexical units whose written form matches `query` under the given mode.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The written form to search for.
+    /// * `mode` - How lemmas are compared with the query.
+    ///
+    /// # Returns
+    ///
+    /// A vector of `LexicalUnitView` instances ranked by how closely their lemma matches the
+    /// query: exact matches first, then matches differing only in case, then only in
+    /// diacritics, then in both. Ties are ordered by lemma and variant.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use plwordnet::{LemmaMatch, PlWordNet};
+    ///
+    /// let plwordnet = PlWordNet::from_file("plwordnet_4_2.xml").unwrap();
+    /// for lu in plwordnet.search_lemma("zolw", LemmaMatch::IgnoreDiacritics) {
+    ///     println!("{} {}", lu.name, lu.variant);
+    /// }
+    /// ```
+    pub fn search_lemma(&self, query: &str, mode: LemmaMatch) -> Vec<LexicalUnitView<'_>> {
+        let key = fold_lemma(query, mode);
+        let mut lemmas: Vec<_> = self.folded_lemma_index.get(&fold_lemma(query, LemmaMatch::IgnoreCaseAndDiacritics))
+            .into_iter()
+            .flatten()
+            .map(|&i| (i, &self.sorted_lemmas[i]))
+            .filter(|(_, lemma)| fold_lemma(lemma, mode) == key)
+            .map(|(i, lemma)| (lemma_rank(lemma, query), i))
+            .collect();
+        // lemma indices follow `sorted_lemmas`, so ties stay in alphabetical order
+        lemmas.sort();
+        lemmas.into_iter()
+            .flat_map(|(_, i)| self.lemma_index[i].iter().filter_map(|&id| self.get_lexical_unit(id)))
+            .collect()
+    }
+
+    /// Returns the lexical units whose written form starts with `prefix`, for example to offer
+    /// completions while a lemma is being typed.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - The beginning of the written form of the lexical units to retrieve.
+    /// * `limit` - The maximum number of lexical units to return.
+    ///
+    /// # Returns
+    ///
+    /// A vector of at most `limit` `LexicalUnitView` instances ordered by lemma and variant.
+    pub fn lemmas_with_prefix(&self, prefix: &str, limit: usize) -> Vec<LexicalUnitView<'_>> {
+        let start = self.sorted_lemmas.partition_point(|lemma| lemma.as_str() < prefix);
+        self.sorted_lemmas[start..]
+            .iter()
+            .take_while(|lemma| lemma.starts_with(prefix))
+            .flat_map(|lemma| self.lexical_units_by_lemma(lemma, None))
+            .take(limit)
+            .collect()
+    }
+
+    /// Returns the lexical units whose written form is within `max_distance` edits of `query`,
+    /// for example to recover from typos.
+    ///
+    /// The distance is the Levenshtein distance counted in characters, so inserting, deleting or
+    /// substituting a single letter, including a letter with a diacritic, costs `1`.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The written form to compare lemmas with.
+    /// * `max_distance` - The maximum edit distance of the returned lexical units.
+    ///
+    /// # Returns
+    ///
+    /// A vector of `(LexicalUnitView, distance)` pairs ordered by distance, lemma and variant.
+    pub fn fuzzy_lemmas(&self, query: &str, max_distance: usize) -> Vec<(LexicalUnitView<'_>, usize)> {
+        let query: Vec<char> = query.chars().collect();
+        let tree = self.lemma_tree.get_or_init(|| LemmaTree::new(&self.sorted_lemmas));
+        let mut lemmas = tree.find(&self.sorted_lemmas, &query, max_distance);
+        // lemma indices follow `sorted_lemmas`, so lemmas at the same distance stay in alphabetical order
+        lemmas.sort();
+        lemmas.into_iter()
+            .flat_map(|(distance, i)| self.lemma_index[i].iter().filter_map(move |&id| Some((self.get_lexical_unit(id)?, distance))))
+            .collect()
+    }
+
+    /// Searches a text field of every lexical unit or synset for a regular expression.
+    ///
+    /// The pattern matches if it is found anywhere in the field, so anchors such as `^` and `$`
+    /// must be used to match the whole field. When the `parallel` feature is enabled the entries
+    /// are scanned on multiple threads.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - The regular expression to search for.
+    /// * `field` - The field the pattern is matched against.
+    ///
+    /// # Returns
+    ///
+    /// A vector of `SearchMatch` instances ordered by id, holding lexical units when `field`
+    /// belongs to lexical units and synsets otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use regex::Regex;
+    /// use plwordnet::{PlWordNet, SearchField};
+    ///
+    /// let plwordnet = PlWordNet::from_file("plwordnet_4_2.xml").unwrap();
+    /// let pattern = Regex::new("ość$").unwrap();
+    /// for found in plwordnet.search_regex(&pattern, SearchField::LexicalUnitName) {
+    ///     println!("{:?}", found);
+    /// }
+    /// ```
+    pub fn search_regex(&self, pattern: &Regex, field: SearchField) -> Vec<SearchMatch<'_>> {
+        match field {
+            SearchField::LexicalUnitName | SearchField::LexicalUnitDesc => {
+                let ids = scan(&self.lexical_units, |lu| pattern.is_match(match field {
+                    SearchField::LexicalUnitName => &lu.name,
+                    _ => &lu.desc,
+                }));
+                ids.into_iter()
+                    .filter_map(|id| self.get_lexical_unit(id))
+                    .map(SearchMatch::LexicalUnit)
+                    .collect()
+            },
+            SearchField::SynsetDefinition | SearchField::SynsetDesc => {
+                let ids = scan(&self.synsets, |synset| pattern.is_match(match field {
+                    SearchField::SynsetDefinition => &synset.definition,
+                    _ => &synset.desc,
+                }));
+                ids.into_iter()
+                    .filter_map(|id| self.get_synset(id))
+                    .map(SearchMatch::Synset)
+                    .collect()
+            },
+        }
+    }
+
+    /// Searches a text field of every lexical unit or synset for a wildcard pattern, in which
+    /// `*` stands for any sequence of characters and `?` for any single character.
+    ///
+    /// Unlike [`PlWordNet::search_regex`], the pattern has to match the whole field, so
+    /// `*ość` finds lemmas ending in `ość` and `*narzędzie*` finds fields mentioning `narzędzie`.
+    ///
+    /// # Arguments
+    ///
+    /// * `glob` - The wildcard pattern to search for.
+    /// * `field` - The field the pattern is matched against.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the matches as returned by [`PlWordNet::search_regex`], or an error
+    /// if the pattern is too large to compile.
+    pub fn search_glob(&self, glob: &str, field: SearchField) -> Result<Vec<SearchMatch<'_>>, regex::Error> {
+        Ok(self.search_regex(&glob_to_regex(glob)?, field))
+    }
+}
+
+/// BK-tree over lemmas, in which every child of a node lies at the edit distance from the node's
+/// lemma it is keyed by. Lemmas are stored as indices into `sorted_lemmas`.
+#[derive(Debug, Default)]
+pub(crate) struct LemmaTree {
+    nodes: Vec<LemmaNode>,
+}
+
+#[derive(Debug)]
+struct LemmaNode {
+    lemma: usize,
+    /// Children as `(distance, node index)` pairs.
+    children: Vec<(usize, usize)>,
+}
+
+impl LemmaTree {
+    fn new(lemmas: &[String]) -> Self {
+        let mut tree = LemmaTree::default();
+        for (i, lemma) in lemmas.iter().enumerate() {
+            tree.insert(lemmas, i, &lemma.chars().collect::<Vec<_>>());
+        }
+        tree
+    }
+
+    fn insert(&mut self, lemmas: &[String], lemma: usize, chars: &[char]) {
+        let index = self.nodes.len();
+        self.nodes.push(LemmaNode { lemma, children: Vec::new() });
+        if index == 0 {
+            return;
+        }
+        let mut node = 0;
+        loop {
+            let distance = edit_distance(chars, &lemmas[self.nodes[node].lemma]);
+            match self.nodes[node].children.iter().find(|&&(d, _)| d == distance) {
+                Some(&(_, child)) => node = child,
+                None => {
+                    self.nodes[node].children.push((distance, index));
+                    return;
+                },
+            }
+        }
+    }
+
+    /// Indices of the lemmas within `max_distance` edits of `query`, with their distances.
+    fn find(&self, lemmas: &[String], query: &[char], max_distance: usize) -> Vec<(usize, usize)> {
+        let mut found = Vec::new();
+        let mut pending = match self.nodes.is_empty() {
+            true => Vec::new(),
+            false => vec![0],
+        };
+        while let Some(node) = pending.pop() {
+            let node = &self.nodes[node];
+            let distance = edit_distance(query, &lemmas[node.lemma]);
+            if distance <= max_distance {
+                found.push((distance, node.lemma));
+            }
+            // by the triangle inequality, matches can only lie below children this close
+            let range = distance.saturating_sub(max_distance)..=distance.saturating_add(max_distance);
+            pending.extend(node.children.iter()
+                .filter(|(d, _)| range.contains(d))
+                .map(|&(_, child)| child));
+        }
+        found
+    }
+}
+
+/// Ids of the entries satisfying `predicate`, in ascending order.
+fn scan<T: Sync>(entries: &HashMap<usize, T>, predicate: impl Fn(&T) -> bool + Sync) -> Vec<usize> {
+    #[cfg(feature = "parallel")]
+    let mut ids: Vec<usize> = {
+        use rayon::prelude::*;
+        entries.par_iter()
+            .filter(|(_, entry)| predicate(entry))
+            .map(|(&id, _)| id)
+            .collect()
+    };
+    #[cfg(not(feature = "parallel"))]
+    let mut ids: Vec<usize> = entries.iter()
+        .filter(|(_, entry)| predicate(entry))
+        .map(|(&id, _)| id)
+        .collect();
+    ids.sort();
+    ids
+}
+
+/// Translates a wildcard pattern into a regular expression matching whole fields.
+pub(crate) fn glob_to_regex(glob: &str) -> Result<Regex, regex::Error> {
+    let mut pattern = String::from("(?s)^");
+    for c in glob.chars() {
+        match c {
+            '*' => pattern.push_str(".*"),
+            '?' => pattern.push('.'),
+            c => pattern.push_str(&regex::escape(c.encode_utf8(&mut [0; 4]))),
+        }
+    }
+    pattern.push('$');
+    Regex::new(&pattern)
+}
+
+/// Normalizes a lemma for comparison under the given mode.
+pub(crate) fn fold_lemma(lemma: &str, mode: LemmaMatch) -> String {
+    let lemma = match mode.ignores_case() {
+        true => lemma.to_lowercase(),
+        false => lemma.to_owned(),
+    };
+    match mode.ignores_diacritics() {
+        true => lemma.chars().map(strip_diacritic).collect(),
+        false => lemma,
+    }
+}
+
+/// Ranks a matching lemma by the differences from the query it was found for: `0` for an exact
+/// match, `1` for case only, `2` for diacritics only and `3` for both.
+fn lemma_rank(lemma: &str, query: &str) -> u8 {
+    if lemma == query {
+        0
+    } else if lemma.to_lowercase() == query.to_lowercase() {
+        1
+    } else if fold_lemma(lemma, LemmaMatch::IgnoreDiacritics) == fold_lemma(query, LemmaMatch::IgnoreDiacritics) {
+        2
+    } else {
+        3
+    }
+}
+
+/// Levenshtein distance between `query` and `lemma` in characters.
+fn edit_distance(query: &[char], lemma: &str) -> usize {
+    let lemma: Vec<char> = lemma.chars().collect();
+    let mut previous: Vec<usize> = (0..=lemma.len()).collect();
+    let mut current = vec![0; lemma.len() + 1];
+    for (i, &q) in query.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, &l) in lemma.iter().enumerate() {
+            let substitution = previous[j] + usize::from(q != l);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[lemma.len()]
+}
+
+fn strip_diacritic(c: char) -> char {
+    match c {
+        'ą' => 'a',
+        'ć' => 'c',
+        'ę' => 'e',
+        'ł' => 'l',
+        'ń' => 'n',
+        'ó' => 'o',
+        'ś' => 's',
+        'ź' | 'ż' => 'z',
+        'Ą' => 'A',
+        'Ć' => 'C',
+        'Ę' => 'E',
+        'Ł' => 'L',
+        'Ń' => 'N',
+        'Ó' => 'O',
+        'Ś' => 'S',
+        'Ź' | 'Ż' => 'Z',
+        c => c,
+    }
+}
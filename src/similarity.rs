@@ -0,0 +1,290 @@
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::io::BufRead;
+use crate::model::{PartOfSpeech, PlWordNet, PlWordNetError};
+
+
+/// Path-based semantic similarity measures over the hypernymy hierarchy, created with
+/// [`PlWordNet::similarity`].
+///
+/// The hierarchy of some parts of speech, verbs in particular, has many separate roots. When root
+/// simulation is enabled for a part of speech, all roots of that part of speech are treated as
+/// hyponyms of a single artificial root, so any two of its synsets are connected. By default roots
+/// are simulated for verbs only.
+pub struct Similarity<'a> {
+    wordnet: &'a PlWordNet,
+    simulate_root: HashSet<PartOfSpeech>,
+    /// Depth of the deepest synset of each part of speech, computed on first use.
+    max_depths: RefCell<HashMap<Option<PartOfSpeech>, usize>>,
+}
+
+impl<'a> Similarity<'a> {
+    /// Enables or disables root simulation for synsets of the given part of speech.
+    pub fn simulate_root(mut self, pos: PartOfSpeech, simulate: bool) -> Self {
+        match simulate {
+            true => self.simulate_root.insert(pos),
+            false => self.simulate_root.remove(&pos),
+        };
+        self
+    }
+
+    /// Computes the path similarity of two synsets, `1 / (distance + 1)`, where `distance` is the
+    /// number of hypernymy relations on the shortest path connecting them through a common
+    /// hypernym.
+    ///
+    /// # Returns
+    ///
+    /// An `Option` containing a score in `(0, 1]`, or `None` if the synsets are not connected.
+    pub fn path(&self, a: usize, b: usize) -> Option<f64> {
+        self.distance(a, b).map(|distance| 1.0 / (distance as f64 + 1.0))
+    }
+
+    /// Computes the Wu-Palmer similarity of two synsets, `2 * depth(lcs) / (len(a) + len(b))`,
+    /// where depths count synsets from the root and `len(x)` is the depth of `lcs` plus the
+    /// distance from `x` to it. The lowest common subsumer giving the highest score is used.
+    ///
+    /// # Returns
+    ///
+    /// An `Option` containing a score in `(0, 1]`, or `None` if the synsets are not connected.
+    pub fn wu_palmer(&self, a: usize, b: usize) -> Option<f64> {
+        let wn = self.wordnet;
+        let (from_a, from_b) = (wn.hypernym_distances(a), wn.hypernym_distances(b));
+        let score = |depth: usize, a: usize, b: usize| {
+            let depth = depth as f64;
+            2.0 * depth / (a as f64 + b as f64 + 2.0 * depth)
+        };
+        let best = wn.lowest_common_subsumer_ids(a, b)
+            .into_iter()
+            .filter_map(|(lcs, depth)| Some(score(depth + 1, *from_a.get(&lcs)?, *from_b.get(&lcs)?)))
+            .max_by(f64::total_cmp);
+        match best {
+            Some(best) => Some(best),
+            None if self.simulates_root(a, b) => Some(score(1, wn.depth(a)? + 1, wn.depth(b)? + 1)),
+            None => None,
+        }
+    }
+
+    /// Computes the Leacock-Chodorow similarity of two synsets, `-ln((distance + 1) / (2 * D))`,
+    /// where `distance` is as in [`Similarity::path`] and `D` is the depth of the deepest synset
+    /// sharing their part of speech.
+    ///
+    /// # Returns
+    ///
+    /// An `Option` containing the score, or `None` if the synsets have different parts of speech
+    /// or are not connected.
+    pub fn leacock_chodorow(&self, a: usize, b: usize) -> Option<f64> {
+        let pos = self.wordnet.synset_pos(a);
+        if pos != self.wordnet.synset_pos(b) {
+            return None;
+        }
+        let distance = self.distance(a, b)?;
+        let max_depth = self.max_depth(pos) + usize::from(self.simulates_root(a, b));
+        Some(-((distance as f64 + 1.0) / (2.0 * max_depth.max(1) as f64)).ln())
+    }
+
+    /// Number of hypernymy relations on the shortest path connecting two synsets through a common
+    /// hypernym, or through the simulated root.
+    fn distance(&self, a: usize, b: usize) -> Option<usize> {
+        let wn = self.wordnet;
+        let (from_a, from_b) = (wn.hypernym_distances(a), wn.hypernym_distances(b));
+        let shortest = from_a.iter()
+            .filter_map(|(id, distance)| from_b.get(id).map(|other| distance + other))
+            .min();
+        match shortest {
+            Some(shortest) => Some(shortest),
+            None if self.simulates_root(a, b) => Some(wn.depth(a)? + wn.depth(b)? + 2),
+            None => None,
+        }
+    }
+
+    fn simulates_root(&self, a: usize, b: usize) -> bool {
+        let pos = self.wordnet.synset_pos(a);
+        pos == self.wordnet.synset_pos(b) && pos.is_some_and(|pos| self.simulate_root.contains(&pos))
+    }
+
+    /// Length of the longest hypernym path of any synset of the given part of speech.
+    fn max_depth(&self, pos: Option<PartOfSpeech>) -> usize {
+        if let Some(&depth) = self.max_depths.borrow().get(&pos) {
+            return depth;
+        }
+        let mut memo = HashMap::new();
+        let depth = self.wordnet.synsets.keys()
+            .filter(|&&id| self.wordnet.synset_pos(id) == pos)
+            .map(|&id| self.longest_hypernym_path(id, &mut memo))
+            .max()
+            .unwrap_or(0);
+        self.max_depths.borrow_mut().insert(pos, depth);
+        depth
+    }
+
+    fn longest_hypernym_path(&self, synset_id: usize, memo: &mut HashMap<usize, Option<usize>>) -> usize {
+        match memo.get(&synset_id) {
+            Some(&Some(depth)) => return depth,
+            // synset is on the current path, the hierarchy has a cycle
+            Some(None) => return 0,
+            None => memo.insert(synset_id, None),
+        };
+        let depth = self.wordnet.hypernym_ids(synset_id)
+            .into_iter()
+            .map(|id| self.longest_hypernym_path(id, memo) + 1)
+            .max()
+            .unwrap_or(0);
+        memo.insert(synset_id, Some(depth));
+        depth
+    }
+}
+
+/// Information content of synsets, `-ln(p)` where `p` is the probability of encountering a synset
+/// or any of its transitive hyponyms among the synsets of the same part of speech.
+///
+/// Frequencies are smoothed by adding one to the count of every synset before they are propagated
+/// to all hypernyms.
+#[derive(Debug, Clone)]
+pub struct InformationContent {
+    values: HashMap<usize, f64>,
+}
+
+impl InformationContent {
+    /// Computes information content from the `tagcount` of the lexical units in each synset.
+    pub fn from_tagcount(wordnet: &PlWordNet) -> Self {
+        let counts = wordnet.synsets.values().map(|synset| {
+            let count: i32 = synset.lexical_units.iter()
+                .filter_map(|id| wordnet.lexical_units.get(id))
+                .map(|lu| lu.tagcount.max(0))
+                .sum();
+            (synset.id, count as f64)
+        });
+        Self::from_synset_counts(wordnet, counts.collect())
+    }
+
+    /// Computes information content from corpus counts of lemmas. The count of each lemma is split
+    /// evenly between the synsets containing it.
+    ///
+    /// # Arguments
+    ///
+    /// * `wordnet` - The plWordNet the synsets belong to.
+    /// * `counts` - Pairs of lemma and the number of its occurrences.
+    pub fn from_lemma_counts<S: AsRef<str>>(
+        wordnet: &PlWordNet,
+        counts: impl IntoIterator<Item=(S, f64)>,
+    ) -> Self {
+        let mut synset_counts = HashMap::new();
+        for (lemma, count) in counts {
+            let synsets = wordnet.synsets_by_lemma(lemma.as_ref(), None);
+            for synset in &synsets {
+                *synset_counts.entry(synset.id).or_default() += count / synsets.len() as f64;
+            }
+        }
+        Self::from_synset_counts(wordnet, synset_counts)
+    }
+
+    /// Computes information content from a frequency file with one lemma per line followed by its
+    /// count, separated by whitespace. Counts have to be finite and not negative. Empty lines and
+    /// lines starting with `#` are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `wordnet` - The plWordNet the synsets belong to.
+    /// * `reader` - A buffered reader yielding the frequency file.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<InformationContent, PlWordNetError>` - The information content table, or an error
+    ///   if the file could not be read or contains a malformed line.
+    pub fn from_reader<R: BufRead>(wordnet: &PlWordNet, reader: R) -> Result<Self, PlWordNetError> {
+        let mut counts = Vec::new();
+        for (number, line) in reader.lines().enumerate() {
+            let line = line.map_err(PlWordNetError::Io)?;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let count = line.rsplit_once(char::is_whitespace)
+                .and_then(|(lemma, count)| Some((lemma.trim_end().to_string(), count.parse::<f64>().ok()?)))
+                .filter(|&(_, count)| count.is_finite() && count >= 0.0);
+            match count {
+                Some(count) => counts.push(count),
+                None => return Err(PlWordNetError::BadFrequencyLine { line: number + 1, content: line.to_string() }),
+            }
+        }
+        Ok(Self::from_lemma_counts(wordnet, counts))
+    }
+
+    fn from_synset_counts(wordnet: &PlWordNet, counts: HashMap<usize, f64>) -> Self {
+        let mut cumulative: HashMap<usize, f64> = HashMap::new();
+        let mut totals: HashMap<Option<PartOfSpeech>, f64> = HashMap::new();
+        for &id in wordnet.synsets.keys() {
+            let count = counts.get(&id).copied().unwrap_or(0.0) + 1.0;
+            *totals.entry(wordnet.synset_pos(id)).or_default() += count;
+            for ancestor in wordnet.hypernym_closure_ids(id) {
+                *cumulative.entry(ancestor).or_default() += count;
+            }
+        }
+        let values = cumulative.into_iter()
+            .filter_map(|(id, count)| Some((id, -(count / totals.get(&wordnet.synset_pos(id))?).ln())))
+            .collect();
+        Self { values }
+    }
+
+    /// Retrieves the information content of the synset with the specified ID.
+    pub fn get(&self, synset_id: usize) -> Option<f64> {
+        self.values.get(&synset_id).copied()
+    }
+}
+
+impl PlWordNet {
+    /// Computes the Resnik similarity of two synsets, the information content of their most
+    /// informative common subsumer.
+    ///
+    /// # Returns
+    ///
+    /// An `Option` containing the score, or `None` if the synsets share no hypernym.
+    pub fn resnik_similarity(&self, a: usize, b: usize, ic: &InformationContent) -> Option<f64> {
+        self.lowest_common_subsumer_ids(a, b)
+            .into_iter()
+            .filter_map(|(id, _)| ic.get(id))
+            .max_by(f64::total_cmp)
+    }
+
+    /// Computes the Lin similarity of two synsets, `2 * resnik / (ic(a) + ic(b))`.
+    ///
+    /// # Returns
+    ///
+    /// An `Option` containing a score in `[0, 1]`, or `None` if the synsets share no hypernym.
+    pub fn lin_similarity(&self, a: usize, b: usize, ic: &InformationContent) -> Option<f64> {
+        let resnik = self.resnik_similarity(a, b, ic)?;
+        let sum = ic.get(a)? + ic.get(b)?;
+        Some(if sum == 0.0 { 1.0 } else { 2.0 * resnik / sum })
+    }
+
+    /// Computes the Jiang-Conrath similarity of two synsets, `1 / (ic(a) + ic(b) - 2 * resnik)`.
+    ///
+    /// # Returns
+    ///
+    /// An `Option` containing the score, which is infinite for synsets with the same information
+    /// content as their subsumer, or `None` if the synsets share no hypernym.
+    pub fn jcn_similarity(&self, a: usize, b: usize, ic: &InformationContent) -> Option<f64> {
+        let resnik = self.resnik_similarity(a, b, ic)?;
+        let distance = ic.get(a)? + ic.get(b)? - 2.0 * resnik;
+        Some(if distance <= 0.0 { f64::INFINITY } else { 1.0 / distance })
+    }
+
+    /// Creates a calculator of path-based similarity measures between synsets of this plWordNet.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use plwordnet::{PartOfSpeech, PlWordNet};
+    ///
+    /// let plwordnet = PlWordNet::from_file("plwordnet_4_2.xml").unwrap();
+    /// let similarity = plwordnet.similarity().simulate_root(PartOfSpeech::Noun, true);
+    /// println!("{:?}", similarity.wu_palmer(1234, 5678));
+    /// ```
+    pub fn similarity(&self) -> Similarity<'_> {
+        Similarity {
+            wordnet: self,
+            simulate_root: HashSet::from([PartOfSpeech::Verb]),
+            max_depths: RefCell::new(HashMap::new()),
+        }
+    }
+}
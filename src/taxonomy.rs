@@ -0,0 +1,217 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use crate::model::{PartOfSpeech, PlWordNet, SynsetView};
+
+
+impl PlWordNet {
+    /// Retrieves every hypernym path of the synset with the specified ID.
+    ///
+    /// Hypernymy is followed through the `hiperonimia` relation type, the reverse of the
+    /// `hiponimia` relation type, and their subtypes. Synsets with several hypernyms yield one path
+    /// per hypernym.
+    ///
+    /// # Arguments
+    ///
+    /// * `synset_id` - The identifier of the synset whose hypernym paths are retrieved.
+    ///
+    /// # Returns
+    ///
+    /// A vector of paths, each starting at a root of the hierarchy and ending at the given synset.
+    /// The vector is empty if no synset exists with the specified ID.
+    pub fn hypernym_paths(&self, synset_id: usize) -> Vec<Vec<SynsetView<'_>>> {
+        self.hypernym_id_paths(synset_id)
+            .into_iter()
+            .map(|path| path.into_iter().filter_map(|id| self.get_synset(id)).collect())
+            .collect()
+    }
+
+    /// Computes the depth of the synset with the specified ID in the hypernymy hierarchy.
+    ///
+    /// # Arguments
+    ///
+    /// * `synset_id` - The identifier of the synset whose depth is computed.
+    ///
+    /// # Returns
+    ///
+    /// An `Option` containing the length of the shortest hypernym path from the synset to a root,
+    /// where roots have depth `0`, or `None` if no synset exists with the specified ID. If every
+    /// hypernym of the synset lies on a cycle, the distance to the farthest hypernym is used.
+    pub fn depth(&self, synset_id: usize) -> Option<usize> {
+        let distances = self.hypernym_distances(synset_id);
+        let roots = distances.iter()
+            .filter(|&(&id, _)| self.hypernym_ids(id).is_empty())
+            .map(|(_, &distance)| distance)
+            .min();
+        roots.or_else(|| distances.values().copied().max())
+    }
+
+    /// Finds the lowest common subsumers of two synsets in the hypernymy hierarchy.
+    ///
+    /// A common subsumer is a synset that is either of the two synsets or one of their transitive
+    /// hypernyms. Because a synset can have several hypernyms, there may be several common
+    /// subsumers none of which is a hypernym of another; all of them are returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - The identifier of the first synset.
+    /// * `b` - The identifier of the second synset.
+    ///
+    /// # Returns
+    ///
+    /// A vector of `(SynsetView, depth)` pairs ordered from the deepest subsumer, empty if the
+    /// synsets share no hypernym or either of them does not exist.
+    pub fn lowest_common_subsumers(&self, a: usize, b: usize) -> Vec<(SynsetView<'_>, usize)> {
+        self.lowest_common_subsumer_ids(a, b)
+            .into_iter()
+            .filter_map(|(id, depth)| self.get_synset(id).map(|synset| (synset, depth)))
+            .collect()
+    }
+
+    /// Returns a lazy breadth-first iterator over the transitive hyponyms of the synset with the
+    /// specified ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `synset_id` - The identifier of the synset whose hyponyms are visited.
+    /// * `max_depth` - An optional limit on the distance from the given synset.
+    ///
+    /// # Returns
+    ///
+    /// An iterator that yields `(depth, SynsetView)` pairs, where direct hyponyms have depth `1`.
+    /// Each synset is visited once, at the smallest depth it can be reached at.
+    pub fn hyponym_closure(
+        &self,
+        synset_id: usize,
+        max_depth: Option<usize>,
+    ) -> impl Iterator<Item=(usize, SynsetView<'_>)> {
+        let traversal = self.traverse(synset_id)
+            .follow(self.hyponymy_types.clone(), self.hypernymy_types.clone());
+        let traversal = match max_depth {
+            Some(max_depth) => traversal.max_depth(max_depth),
+            None => traversal,
+        };
+        traversal.into_iter().map(|(depth, synset, _)| (depth, synset))
+    }
+
+    /// Part of speech of the first lexical unit of a synset.
+    pub(crate) fn synset_pos(&self, synset_id: usize) -> Option<PartOfSpeech> {
+        self.synsets.get(&synset_id)?
+            .lexical_units
+            .first()
+            .and_then(|id| self.lexical_units.get(id))
+            .and_then(|lu| lu.part_of_speech)
+    }
+
+    /// Ids and depths of the lowest common subsumers of two synsets, deepest first.
+    pub(crate) fn lowest_common_subsumer_ids(&self, a: usize, b: usize) -> Vec<(usize, usize)> {
+        let ancestors = self.hypernym_closure_ids(b);
+        let common: HashSet<_> = self.hypernym_closure_ids(a)
+            .into_iter()
+            .filter(|id| ancestors.contains(id))
+            .collect();
+        let subsuming: HashSet<_> = common.iter()
+            .flat_map(|&id| self.hypernym_ids(id))
+            .filter(|id| common.contains(id))
+            .collect();
+        let depths = self.depths_within(&common);
+        let mut lowest: Vec<_> = common.difference(&subsuming)
+            .filter_map(|&id| depths.get(&id).copied().or_else(|| self.depth(id)).map(|depth| (id, depth)))
+            .collect();
+        lowest.sort_by(|(a, a_depth), (b, b_depth)| b_depth.cmp(a_depth).then(a.cmp(b)));
+        lowest
+    }
+
+    /// Shortest distance from a synset to itself and each of its transitive hypernyms.
+    pub(crate) fn hypernym_distances(&self, synset_id: usize) -> HashMap<usize, usize> {
+        if !self.synsets.contains_key(&synset_id) {
+            return HashMap::new();
+        }
+        self.traverse(synset_id)
+            .follow(self.hypernymy_types.clone(), self.hyponymy_types.clone())
+            .into_iter()
+            .map(|(distance, synset, _)| (synset.id, distance))
+            .chain([(synset_id, 0)])
+            .collect()
+    }
+
+    /// Depths of the synsets in `closure`, which must contain every hypernym of its synsets,
+    /// computed with a single breadth-first search down from the roots it contains. Synsets whose
+    /// hypernyms all lie on a cycle are left out.
+    fn depths_within(&self, closure: &HashSet<usize>) -> HashMap<usize, usize> {
+        let mut depths: HashMap<_, _> = closure.iter()
+            .filter(|&&id| self.hypernym_ids(id).is_empty())
+            .map(|&id| (id, 0))
+            .collect();
+        let mut queue: VecDeque<_> = depths.keys().copied().collect();
+        while let Some(id) = queue.pop_front() {
+            let depth = depths[&id] + 1;
+            for hyponym in self.related_synset_ids(id, &self.hyponymy_types, &self.hypernymy_types) {
+                if closure.contains(&hyponym) && !depths.contains_key(&hyponym) {
+                    depths.insert(hyponym, depth);
+                    queue.push_back(hyponym);
+                }
+            }
+        }
+        depths
+    }
+
+    /// Ids of a synset and all of its transitive hypernyms.
+    pub(crate) fn hypernym_closure_ids(&self, synset_id: usize) -> HashSet<usize> {
+        let mut closure = HashSet::new();
+        if !self.synsets.contains_key(&synset_id) {
+            return closure;
+        }
+        let mut stack = vec![synset_id];
+        while let Some(id) = stack.pop() {
+            if closure.insert(id) {
+                stack.extend(self.hypernym_ids(id));
+            }
+        }
+        closure
+    }
+
+    /// Ids of every hypernym path of a synset, each ordered from a root to the synset itself.
+    pub(crate) fn hypernym_id_paths(&self, synset_id: usize) -> Vec<Vec<usize>> {
+        let mut paths = Vec::new();
+        if self.synsets.contains_key(&synset_id) {
+            self.collect_hypernym_paths(&mut vec![synset_id], &mut paths);
+        }
+        paths
+    }
+
+    fn collect_hypernym_paths(&self, path: &mut Vec<usize>, paths: &mut Vec<Vec<usize>>) {
+        let hypernyms: Vec<_> = self.hypernym_ids(path[path.len() - 1])
+            .into_iter()
+            .filter(|id| !path.contains(id))
+            .collect();
+        if hypernyms.is_empty() {
+            paths.push(path.iter().rev().copied().collect());
+        }
+        for hypernym in hypernyms {
+            path.push(hypernym);
+            self.collect_hypernym_paths(path, paths);
+            path.pop();
+        }
+    }
+
+    /// Ids of the direct hypernyms of a synset.
+    pub(crate) fn hypernym_ids(&self, synset_id: usize) -> Vec<usize> {
+        self.related_synset_ids(synset_id, &self.hypernymy_types, &self.hyponymy_types)
+    }
+
+    /// Ids of the synsets reached from a synset through an outgoing relation of one of the
+    /// `outgoing` types or an incoming relation of one of the `incoming` types. Relations to
+    /// synsets which are not loaded are skipped.
+    fn related_synset_ids(
+        &self,
+        synset_id: usize,
+        outgoing: &HashSet<usize>,
+        incoming: &HashSet<usize>,
+    ) -> Vec<usize> {
+        let mut seen = HashSet::new();
+        self.synset_edges(synset_id, |relation| outgoing.contains(&relation), |relation| incoming.contains(&relation))
+            .map(|(id, _)| id)
+            .filter(|id| self.synsets.contains_key(id))
+            .filter(|&id| seen.insert(id))
+            .collect()
+    }
+}
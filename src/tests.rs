@@ -1,7 +1,19 @@
 use once_cell::sync::Lazy;
-use crate::model::PlWordNet;
+use crate::model::{Domain, Language, PartOfSpeech, PlWordNet, PlWordNetError, Workstate};
+use crate::emotion::{Emotion, Polarity, Strength, Valuation};
+use crate::gloss::{Gloss, GlossExample};
+use crate::path::RelationStep;
+use crate::query::{Query, QueryError};
+use crate::search::{LemmaMatch, SearchField, SearchMatch};
+use crate::similarity::InformationContent;
+use crate::traversal::Direction;
+use crate::workstate::LoadOptions;
 
 
+pub(crate) static SAMPLE: Lazy<PlWordNet> = Lazy::new(||
+    PlWordNet::from_bytes(include_bytes!("../fixtures/plwordnet_sample.xml")).unwrap()
+);
+
 pub(crate) static WORDNET: Lazy<PlWordNet> = Lazy::new(||
     PlWordNet::from_file("plwordnet_4_2.xml").unwrap()
 );
@@ -16,3 +28,668 @@ fn loading() {
     assert_eq!(meta.synset_relations, 1477851);
     assert_eq!(meta.lexical_relations, 393137);
 }
+
+
+/// Writes `contents` to a file in the temporary directory, named uniquely for this process.
+fn temp_file(name: &str, contents: &[u8]) -> std::path::PathBuf {
+    let path = std::env::temp_dir().join(format!("plwordnet_{}_{}", std::process::id(), name));
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+#[test]
+fn loading_missing_file() {
+    let result = PlWordNet::from_file("does_not_exist.xml");
+    assert!(matches!(result, Err(PlWordNetError::Io(_))));
+}
+
+#[test]
+fn loading_errors() {
+    let load = |name, xml: &[u8]| {
+        let path = temp_file(name, xml);
+        let result = PlWordNet::from_file(&path);
+        std::fs::remove_file(path).unwrap();
+        result
+    };
+    let result = load("xml.xml", b"<array-list><lexical-unit id=1/></array-list>");
+    assert!(matches!(result, Err(PlWordNetError::Xml { .. })), "{:?}", result);
+    let result = load("attribute.xml", b"<array-list><lexical-unit id=\"x1\"/></array-list>");
+    assert!(matches!(result, Err(PlWordNetError::BadAttribute { .. })), "{:?}", result);
+    let result = load("element.xml", b"<array-list><castle></castle></array-list>");
+    assert!(matches!(result, Err(PlWordNetError::UnknownElement { ref tag, .. }) if tag == "castle"), "{:?}", result);
+    let result = load("root.xml", b"<synset id=\"1\"></synset>");
+    assert!(matches!(result, Err(PlWordNetError::MissingRoot)), "{:?}", result);
+    let result = load("context.xml", b"<array-list><unit-id>1</unit-id></array-list>");
+    assert!(matches!(result, Err(PlWordNetError::DanglingContext { ref tag, .. }) if tag == "unit-id"), "{:?}", result);
+}
+
+#[test]
+fn loading_bytes() {
+    let meta = SAMPLE.get_metadata();
+    assert_eq!(meta.version, "4.2");
+    assert_eq!(meta.lexical_units, 10);
+    assert_eq!(meta.synsets, 10);
+    assert_eq!(meta.relation_types, 9);
+    assert_eq!(meta.synset_relations, 16);
+    assert_eq!(meta.lexical_relations, 1);
+    assert_eq!(SAMPLE.get_synset(101).unwrap().lexical_units[0].name, "zamek");
+}
+
+#[test]
+fn loading_bad_attribute() {
+    let xml = br#"<array-list><lexical-unit id="x1" name="zamek"/></array-list>"#;
+    match PlWordNet::from_bytes(xml) {
+        Err(PlWordNetError::BadAttribute { tag, attribute, value }) => {
+            assert_eq!((tag.as_str(), attribute.as_str(), value.as_str()), ("lexical-unit", "id", "x1"));
+        },
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert!(matches!(PlWordNet::from_bytes(b"<synset id=\"1\"></synset>"), Err(PlWordNetError::MissingRoot)));
+}
+
+/// Loads the sample written to a temporary file in compressed form.
+#[cfg(any(feature = "gzip", feature = "xz", feature = "zstd"))]
+fn load_compressed(name: &str, compressed: &[u8]) -> PlWordNet {
+    let path = temp_file(name, compressed);
+    let wordnet = PlWordNet::from_file(&path);
+    std::fs::remove_file(path).unwrap();
+    wordnet.unwrap()
+}
+
+#[cfg(feature = "gzip")]
+#[test]
+fn loading_gzip() {
+    use std::io::Write;
+    use flate2::write::GzEncoder;
+
+    let mut encoder = GzEncoder::new(Vec::new(), Default::default());
+    encoder.write_all(include_bytes!("../fixtures/plwordnet_sample.xml")).unwrap();
+    let wordnet = load_compressed("sample.xml.gz", &encoder.finish().unwrap());
+    assert_eq!(wordnet.get_metadata().synsets, SAMPLE.get_metadata().synsets);
+}
+
+#[cfg(feature = "xz")]
+#[test]
+fn loading_xz() {
+    use std::io::Write;
+    use xz2::write::XzEncoder;
+
+    let mut encoder = XzEncoder::new(Vec::new(), 6);
+    encoder.write_all(include_bytes!("../fixtures/plwordnet_sample.xml")).unwrap();
+    let wordnet = load_compressed("sample.xml.xz", &encoder.finish().unwrap());
+    assert_eq!(wordnet.get_metadata().synsets, SAMPLE.get_metadata().synsets);
+}
+
+#[cfg(feature = "zstd")]
+#[test]
+fn loading_zstd() {
+    let compressed = zstd::encode_all(&include_bytes!("../fixtures/plwordnet_sample.xml")[..], 0).unwrap();
+    let wordnet = load_compressed("sample.xml.zst", &compressed);
+    assert_eq!(wordnet.get_metadata().synsets, SAMPLE.get_metadata().synsets);
+}
+
+/// Loads a file starting with the given magic bytes, expecting its compression to be rejected.
+#[cfg(not(all(feature = "gzip", feature = "xz", feature = "zstd")))]
+fn load_unsupported(name: &str, magic: &[u8]) -> &'static str {
+    let path = temp_file(name, magic);
+    let result = PlWordNet::from_file(&path);
+    std::fs::remove_file(path).unwrap();
+    match result {
+        Err(PlWordNetError::UnsupportedCompression(format)) => format,
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[cfg(not(feature = "gzip"))]
+#[test]
+fn loading_gzip_unsupported() {
+    assert_eq!(load_unsupported("unsupported.xml.gz", &[0x1f, 0x8b, 0x08, 0x00]), "gzip");
+}
+
+#[cfg(not(feature = "xz"))]
+#[test]
+fn loading_xz_unsupported() {
+    assert_eq!(load_unsupported("unsupported.xml.xz", &[0xfd, b'7', b'z', b'X', b'Z', 0x00]), "xz");
+}
+
+#[cfg(not(feature = "zstd"))]
+#[test]
+fn loading_zstd_unsupported() {
+    assert_eq!(load_unsupported("unsupported.xml.zst", &[0x28, 0xb5, 0x2f, 0xfd]), "zstd");
+}
+
+#[test]
+fn lemma_lookup() {
+    let ids: Vec<_> = SAMPLE.lexical_units_by_lemma("zamek", None).map(|lu| lu.id).collect();
+    assert_eq!(ids, vec![1, 2]);
+    assert_eq!(SAMPLE.lexical_units_by_lemma("zamek", Some(PartOfSpeech::Verb)).count(), 0);
+    assert_eq!(SAMPLE.lexical_units_by_lemma("zamki", None).count(), 0);
+
+    let ids: Vec<_> = SAMPLE.synsets_by_lemma("zamek", Some(PartOfSpeech::Noun)).iter().map(|s| s.id).collect();
+    assert_eq!(ids, vec![101, 102]);
+}
+
+#[test]
+fn synset_of_lexical_unit() {
+    assert_eq!(SAMPLE.get_lexical_unit(2).unwrap().synset_id, Some(102));
+    assert_eq!(SAMPLE.synset_of(5).unwrap().id, 105);
+    assert!(SAMPLE.synset_of(999).is_none());
+}
+
+#[test]
+fn synset_adjacency() {
+    let hypernyms: Vec<_> = SAMPLE.synset_relations_from(101, [11])
+        .map(|sr| sr.child.unwrap().id)
+        .collect();
+    assert_eq!(hypernyms, vec![103]);
+    assert_eq!(SAMPLE.synset_relations_from(104, []).count(), 2);
+    assert_eq!(SAMPLE.synset_relations_to(104, [11]).count(), 2);
+    assert_eq!(SAMPLE.synset_relations_to(104, [10]).count(), 0);
+    assert_eq!(SAMPLE.synset_relations_from(104, [10, 11]).count(), 2);
+}
+
+#[test]
+fn lexical_adjacency() {
+    let derived: Vec<_> = SAMPLE.lexical_relations_from(9, [56])
+        .map(|lr| lr.child.unwrap().name)
+        .collect();
+    assert_eq!(derived, vec!["zamek"]);
+    assert_eq!(SAMPLE.lexical_relations_to(1, []).count(), 1);
+    assert_eq!(SAMPLE.lexical_relations_to(1, [10]).count(), 0);
+    assert_eq!(SAMPLE.lexical_relations_from(1, []).count(), 0);
+}
+
+#[test]
+fn part_of_speech() {
+    let castle = SAMPLE.get_lexical_unit(8).unwrap();
+    assert_eq!(castle.pos, "rzeczownik pwn");
+    assert_eq!(castle.part_of_speech, Some(PartOfSpeech::Noun));
+    assert_eq!(castle.language, Language::EN);
+    let zamkowy = SAMPLE.get_lexical_unit(9).unwrap();
+    assert_eq!(zamkowy.part_of_speech, Some(PartOfSpeech::Adjective));
+    assert_eq!(zamkowy.language, Language::PL);
+}
+
+#[test]
+fn relation_type_tests() {
+    let hyponymy = SAMPLE.get_relation_type(10).unwrap();
+    let tests: Vec<_> = hyponymy.tests().collect();
+    assert_eq!(tests.len(), 1);
+    assert_eq!(tests[0].pos, "rzeczownik");
+    assert_eq!(tests[0].instantiate("żółw", "gad"), "żółw jest rodzajem gad");
+    assert_eq!(SAMPLE.get_relation_type(11).unwrap().tests().count(), 0);
+
+    let wordnet = PlWordNet::from_bytes(br#"<array-list>
+        <relationtypes id="1" name="a&amp;b"><test text="&lt;x#&gt; &amp; &lt;y#&gt;" pos="rzeczownik"/></relationtypes>
+    </array-list>"#).unwrap();
+    let relation = wordnet.get_relation_type(1).unwrap();
+    assert_eq!(relation.name, "a&amp;b");
+    assert_eq!(relation.tests().next().unwrap().text, "<x#> & <y#>");
+
+    let result = PlWordNet::from_bytes(br#"<array-list>
+        <relationtypes id="1"><test text="&bogus; &lt;x#&gt;" pos="rzeczownik"/></relationtypes>
+    </array-list>"#);
+    match result {
+        Err(PlWordNetError::BadAttribute { tag, attribute, value }) => {
+            assert_eq!((tag.as_str(), attribute.as_str(), value.as_str()), ("test", "text", "&bogus; &lt;x#&gt;"));
+        },
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn relation_type_hierarchy() {
+    let hyponymy = SAMPLE.relation_type_by_name("hiponimia").unwrap();
+    assert_eq!(hyponymy.reverse_type().unwrap().name, "hiperonimia");
+    assert!(hyponymy.parent_type().is_none());
+
+    let meronymy = SAMPLE.relation_type_by_shortcut("mero").unwrap();
+    let sub_types: Vec<_> = meronymy.sub_types().map(|rt| rt.id).collect();
+    assert_eq!(sub_types, vec![20]);
+
+    let part = SAMPLE.relation_type_by_name("część").unwrap();
+    assert_eq!(part.id, 20);
+    assert_eq!(part.parent_type().unwrap().name, "meronimia");
+    assert_eq!(part.reverse_type().unwrap().shortcut, "holo:cz");
+    assert!(SAMPLE.relation_type_by_name("derywacyjność").unwrap().reverse_type().is_none());
+}
+
+#[test]
+fn hypernymy() {
+    let paths: Vec<Vec<_>> = SAMPLE.hypernym_paths(110).iter()
+        .map(|path| path.iter().map(|s| s.id).collect())
+        .collect();
+    assert_eq!(paths, vec![vec![104, 103, 101, 110], vec![104, 103, 110]]);
+    assert_eq!(SAMPLE.depth(110), Some(2));
+    assert_eq!(SAMPLE.depth(104), Some(0));
+    assert_eq!(SAMPLE.depth(999), None);
+
+    let closure: Vec<_> = SAMPLE.hyponym_closure(104, None).map(|(d, s)| (d, s.id)).collect();
+    assert_eq!(closure, vec![(1, 103), (1, 102), (2, 101), (2, 110)]);
+    assert_eq!(SAMPLE.hyponym_closure(104, Some(1)).count(), 2);
+}
+
+#[test]
+fn hypernymy_diamonds() {
+    // 30 stacked diamonds: 2^30 hypernym paths lead from the bottom synset to the root
+    let mut xml = String::from(r#"<array-list><relationtypes id="11" name="hiperonimia"/><synset id="1"></synset>"#);
+    for i in 0..30 {
+        let (top, left, right, bottom) = (3 * i + 1, 3 * i + 2, 3 * i + 3, 3 * i + 4);
+        xml += &format!(r#"<synset id="{left}"></synset><synset id="{right}"></synset><synset id="{bottom}"></synset>
+            <synsetrelations parent="{left}" child="{top}" relation="11"/>
+            <synsetrelations parent="{right}" child="{top}" relation="11"/>
+            <synsetrelations parent="{bottom}" child="{left}" relation="11"/>
+            <synsetrelations parent="{bottom}" child="{right}" relation="11"/>"#);
+    }
+    xml += "</array-list>";
+    let wordnet = PlWordNet::from_bytes(xml.as_bytes()).unwrap();
+    assert_eq!(wordnet.depth(91), Some(60));
+    assert_eq!(wordnet.depth(89), Some(59));
+    let lcs: Vec<_> = wordnet.lowest_common_subsumers(89, 90).into_iter().map(|(s, d)| (s.id, d)).collect();
+    assert_eq!(lcs, vec![(88, 58)]);
+    let lcs: Vec<_> = wordnet.lowest_common_subsumers(91, 91).into_iter().map(|(s, d)| (s.id, d)).collect();
+    assert_eq!(lcs, vec![(91, 60)]);
+    assert!(wordnet.similarity().wu_palmer(89, 90).is_some());
+    assert!(wordnet.similarity().leacock_chodorow(2, 91).is_some());
+}
+
+#[test]
+fn missing_hypernym() {
+    // both synsets name the same hypernym, which is not part of the data
+    let wordnet = PlWordNet::from_bytes(br#"<array-list>
+        <synset id="1"></synset><synset id="2"></synset>
+        <relationtypes id="11" name="hiperonimia"/>
+        <synsetrelations parent="1" child="99" relation="11"/>
+        <synsetrelations parent="2" child="99" relation="11"/>
+    </array-list>"#).unwrap();
+    assert_eq!(wordnet.depth(1), Some(0));
+    assert!(wordnet.hypernym_paths(1).iter().all(|path| path.len() == 1));
+    let similarity = wordnet.similarity();
+    assert_eq!(similarity.path(1, 2), None);
+    assert_eq!(similarity.wu_palmer(1, 2), None);
+    assert_eq!(similarity.leacock_chodorow(1, 2), None);
+    let ic = InformationContent::from_tagcount(&wordnet);
+    assert_eq!(ic.get(99), None);
+    assert_eq!(wordnet.resnik_similarity(1, 2, &ic), None);
+}
+
+#[test]
+fn traversal() {
+    let visited: Vec<_> = SAMPLE.traverse(105)
+        .relations([11])
+        .into_iter()
+        .map(|(depth, synset, via)| (depth, synset.id, via.name))
+        .collect();
+    assert_eq!(visited, vec![(1, 106, "hiperonimia"), (2, 107, "hiperonimia")]);
+    assert_eq!(SAMPLE.traverse(105).relations([]).into_iter().count(), SAMPLE.traverse(105).into_iter().count());
+
+    let visited: Vec<_> = SAMPLE.traverse(103)
+        .relations([10])
+        .direction(Direction::Incoming)
+        .max_depth(1)
+        .into_iter()
+        .map(|(_, synset, _)| synset.id)
+        .collect();
+    assert_eq!(visited, vec![104]);
+
+    let visited: Vec<_> = SAMPLE.traverse(104)
+        .depth_first()
+        .relations([10])
+        .filter(|synset| synset.id != 102)
+        .into_iter()
+        .map(|(depth, synset, _)| (depth, synset.id))
+        .collect();
+    assert_eq!(visited, vec![(1, 103), (2, 101), (3, 110)]);
+
+    // a relation of an unknown type does not hide a synset also reached through a known one
+    let wordnet = PlWordNet::from_bytes(br#"<array-list>
+        <synset id="1"></synset><synset id="2"></synset>
+        <relationtypes id="11" name="hiperonimia"/>
+        <synsetrelations parent="1" child="2" relation="99"/>
+        <synsetrelations parent="1" child="2" relation="11"/>
+    </array-list>"#).unwrap();
+    let visited: Vec<_> = wordnet.traverse(1).into_iter().map(|(_, synset, via)| (synset.id, via.id)).collect();
+    assert_eq!(visited, vec![(2, 11)]);
+    let visited: Vec<_> = wordnet.traverse(1).depth_first().into_iter().map(|(_, synset, via)| (synset.id, via.id)).collect();
+    assert_eq!(visited, vec![(2, 11)]);
+}
+
+#[test]
+fn shortest_paths() {
+    let path: Vec<_> = SAMPLE.shortest_path(105, 107, [11]).unwrap().iter()
+        .map(|sr| (sr.parent.as_ref().unwrap().id, sr.child.as_ref().unwrap().id))
+        .collect();
+    assert_eq!(path, vec![(105, 106), (106, 107)]);
+    assert_eq!(SAMPLE.shortest_path(101, 102, []).unwrap().len(), 3);
+    assert_eq!(SAMPLE.shortest_path(101, 101, []).unwrap().len(), 0);
+    assert!(SAMPLE.shortest_path(101, 105, []).is_none());
+
+    let path = SAMPLE.shortest_lexical_path(9, 10, []).unwrap();
+    assert_eq!(path.len(), 2);
+    assert!(matches!(path[0], RelationStep::Lexical(_)));
+    assert!(matches!(path[1], RelationStep::Synset(_)));
+    assert!(SAMPLE.shortest_lexical_path(9, 10, [10, 11]).is_none());
+}
+
+#[test]
+fn lowest_common_subsumers() {
+    let lcs: Vec<_> = SAMPLE.lowest_common_subsumers(101, 102).into_iter().map(|(s, d)| (s.id, d)).collect();
+    assert_eq!(lcs, vec![(104, 0)]);
+    let lcs: Vec<_> = SAMPLE.lowest_common_subsumers(110, 101).into_iter().map(|(s, d)| (s.id, d)).collect();
+    assert_eq!(lcs, vec![(101, 2)]);
+    assert!(SAMPLE.lowest_common_subsumers(101, 105).is_empty());
+
+    // a hypernym which is not loaded is neither a root nor a common subsumer
+    let wordnet = PlWordNet::from_bytes(br#"<array-list>
+        <synset id="1"></synset><synset id="2"></synset><synset id="3"></synset><synset id="4"></synset>
+        <relationtypes id="11" name="hiperonimia"/>
+        <synsetrelations parent="1" child="99" relation="11"/>
+        <synsetrelations parent="2" child="99" relation="11"/>
+        <synsetrelations parent="3" child="99" relation="11"/>
+        <synsetrelations parent="1" child="4" relation="11"/>
+        <synsetrelations parent="2" child="4" relation="11"/>
+    </array-list>"#).unwrap();
+    assert_eq!(wordnet.lowest_common_subsumer_ids(1, 2), vec![(4, 0)]);
+    assert!(wordnet.lowest_common_subsumer_ids(1, 3).is_empty());
+    assert!(wordnet.lowest_common_subsumers(1, 3).is_empty());
+
+    // 3 and 4 both have the unrelated hypernyms 1 and 2
+    let wordnet = PlWordNet::from_bytes(br#"<array-list>
+        <synset id="1"></synset><synset id="2"></synset>
+        <synset id="3"></synset><synset id="4"></synset>
+        <relationtypes id="11" name="hiperonimia"/>
+        <synsetrelations parent="3" child="1" relation="11"/>
+        <synsetrelations parent="3" child="2" relation="11"/>
+        <synsetrelations parent="4" child="1" relation="11"/>
+        <synsetrelations parent="4" child="2" relation="11"/>
+    </array-list>"#).unwrap();
+    let lcs: Vec<_> = wordnet.lowest_common_subsumers(3, 4).into_iter().map(|(s, d)| (s.id, d)).collect();
+    assert_eq!(lcs, vec![(1, 0), (2, 0)]);
+}
+
+#[test]
+fn similarity() {
+    let similarity = SAMPLE.similarity();
+    assert_eq!(similarity.path(101, 102), Some(0.25));
+    assert_eq!(similarity.path(101, 101), Some(1.0));
+    assert_eq!(similarity.wu_palmer(101, 102), Some(0.4));
+    assert_eq!(similarity.wu_palmer(110, 101), Some(6.0 / 7.0));
+    assert_eq!(similarity.leacock_chodorow(101, 102), Some(-(4.0f64 / 6.0).ln()));
+    assert_eq!(similarity.path(101, 105), None);
+    assert_eq!(similarity.leacock_chodorow(101, 109), None);
+
+    let similarity = SAMPLE.similarity().simulate_root(PartOfSpeech::Noun, true);
+    assert_eq!(similarity.path(101, 105), Some(1.0 / 7.0));
+}
+
+#[test]
+fn information_content() {
+    let close = |a: f64, b: f64| (a - b).abs() < 1e-12;
+    let ic = InformationContent::from_tagcount(&SAMPLE);
+    assert!(close(ic.get(104).unwrap(), (49.0f64 / 32.0).ln()));
+    assert_eq!(SAMPLE.resnik_similarity(101, 102, &ic), ic.get(104));
+    let lin = SAMPLE.lin_similarity(101, 102, &ic).unwrap();
+    assert!(close(lin, 2.0 * (49.0f64 / 32.0).ln() / ((49.0f64 / 16.0).ln() + (49.0f64 / 8.0).ln())));
+    assert_eq!(SAMPLE.jcn_similarity(101, 101, &ic), Some(f64::INFINITY));
+    assert_eq!(SAMPLE.resnik_similarity(101, 105, &ic), None);
+
+    let ic = InformationContent::from_reader(&SAMPLE, "# lemma count\nzamek 10\nżółw 3\n".as_bytes()).unwrap();
+    assert!(close(ic.get(104).unwrap(), (22.0f64 / 15.0).ln()));
+    match InformationContent::from_reader(&SAMPLE, "zamek 10\n\nzamek dziesięć\n".as_bytes()) {
+        Err(PlWordNetError::BadFrequencyLine { line, content }) => {
+            assert_eq!(line, 3);
+            assert_eq!(content, "zamek dziesięć");
+        },
+        other => panic!("unexpected result: {:?}", other.map(|_| ())),
+    }
+    for count in ["NaN", "inf", "-inf", "-100"] {
+        let line = format!("zamek {}", count);
+        let result = InformationContent::from_reader(&SAMPLE, line.as_bytes());
+        assert!(matches!(result, Err(PlWordNetError::BadFrequencyLine { line: 1, .. })), "{}", count);
+    }
+}
+
+#[test]
+fn interlingual_equivalents() {
+    let english: Vec<_> = SAMPLE.english_equivalents(101).into_iter()
+        .map(|(synset, relation)| (synset.id, relation.name))
+        .collect();
+    assert_eq!(english, vec![(108, "Syn_plWN-PWN")]);
+    let polish: Vec<_> = SAMPLE.polish_equivalents(108).into_iter()
+        .map(|(synset, relation)| (synset.id, relation.name))
+        .collect();
+    assert_eq!(polish, vec![(101, "Syn_PWN-plWN")]);
+    assert!(SAMPLE.english_equivalents(102).is_empty());
+    assert!(SAMPLE.polish_equivalents(101).is_empty());
+
+    // a synset without lexical units has no language, so it is neither Polish nor English
+    let wordnet = PlWordNet::from_bytes(br#"<array-list>
+        <lexical-unit id="1" name="castle" pos="rzeczownik pwn"/>
+        <synset id="1"><unit-id>1</unit-id></synset>
+        <synset id="2"></synset>
+        <relationtypes id="212" name="Syn_PWN-plWN"/>
+        <synsetrelations parent="1" child="2" relation="212"/>
+    </array-list>"#).unwrap();
+    assert!(wordnet.polish_equivalents(1).is_empty());
+    assert!(wordnet.english_equivalents(2).is_empty());
+    assert_eq!(wordnet.get_synset(2).unwrap().language, Language::PL);
+}
+
+#[test]
+fn gloss() {
+    let gloss = SAMPLE.get_lexical_unit(1).unwrap().gloss();
+    assert_eq!(gloss.definition, Some("budowla obronna."));
+    assert_eq!(gloss.qualifiers, vec!["hist.", "arch."]);
+    assert_eq!(gloss.examples, vec![GlossExample { text: "Zwiedziliśmy zamek w Malborku.", source: "W" }]);
+    assert_eq!(gloss.links, vec!["https://pl.wikipedia.org/wiki/Zamek"]);
+    assert_eq!(gloss.remainder, vec!["uwaga"]);
+
+    let gloss = Gloss::parse("##A1: {strach; złość} - s [Uciekł przed psem.] ##A2: {strach} - m");
+    assert_eq!(gloss.annotations.len(), 2);
+    assert_eq!(gloss.annotations[0].label, "A1");
+    assert_eq!(gloss.annotations[0].text, "{strach; złość} - s [Uciekł przed psem.]");
+    assert_eq!(Gloss::parse(""), Gloss::default());
+}
+
+#[test]
+fn emotion_annotations() {
+    let annotations = SAMPLE.get_lexical_unit(5).unwrap().emotions();
+    assert_eq!(annotations.len(), 2);
+    assert_eq!(annotations[0].annotator, "A1");
+    assert_eq!(annotations[0].polarity, Some(Polarity::Positive));
+    assert_eq!(annotations[0].strength, Some(Strength::Moderate));
+    assert_eq!(annotations[0].emotions, vec![Emotion::Joy, Emotion::Trust]);
+    assert_eq!(annotations[0].valuations, vec![Valuation::Beauty]);
+    assert_eq!(annotations[0].examples, vec!["Żółw jest uroczy."]);
+    assert_eq!(annotations[1].strength, Some(Strength::Strong));
+    assert_eq!(annotations[1].valuations, vec![Valuation::Beauty, Valuation::Utility]);
+    assert_eq!(annotations[1].examples.len(), 2);
+
+    let neutral = SAMPLE.get_lexical_unit(7).unwrap().emotions();
+    assert_eq!(neutral[0].polarity, Some(Polarity::Neutral));
+    assert_eq!(neutral[0].strength, None);
+    assert!(SAMPLE.get_lexical_unit(3).unwrap().emotions().is_empty());
+
+    assert_eq!(SAMPLE.lexical_units_with_polarity(Polarity::Positive).map(|lu| lu.id).collect::<Vec<_>>(), vec![5]);
+    assert_eq!(SAMPLE.lexical_units_with_polarity(Polarity::Negative).map(|lu| lu.id).collect::<Vec<_>>(), vec![6]);
+    assert_eq!(SAMPLE.lexical_units_with_emotion(Emotion::Joy).map(|lu| lu.id).collect::<Vec<_>>(), vec![5]);
+    assert_eq!(SAMPLE.lexical_units_with_emotion(Emotion::Fear).map(|lu| lu.id).collect::<Vec<_>>(), vec![6]);
+    assert_eq!(SAMPLE.lexical_units_with_emotion(Emotion::Surprise).count(), 0);
+}
+
+#[test]
+fn normalized_lemma_search() {
+    let ids = |query, mode| SAMPLE.search_lemma(query, mode).iter().map(|lu| lu.id).collect::<Vec<_>>();
+    assert_eq!(ids("zolw", LemmaMatch::Exact), Vec::<usize>::new());
+    assert_eq!(ids("zolw", LemmaMatch::IgnoreDiacritics), vec![5]);
+    assert_eq!(ids("ZAMEK", LemmaMatch::IgnoreDiacritics), Vec::<usize>::new());
+    assert_eq!(ids("ZAMEK", LemmaMatch::IgnoreCase), vec![1, 2]);
+    assert_eq!(ids("ZÓLW", LemmaMatch::IgnoreCaseAndDiacritics), vec![5]);
+
+    let wordnet = PlWordNet::from_bytes("<array-list>
+        <lexical-unit id=\"1\" name=\"Żółw\" variant=\"1\"/>
+        <lexical-unit id=\"2\" name=\"zolw\" variant=\"1\"/>
+        <lexical-unit id=\"3\" name=\"Zolw\" variant=\"1\"/>
+        <lexical-unit id=\"4\" name=\"żółw\" variant=\"2\"/>
+        <lexical-unit id=\"5\" name=\"żółw\" variant=\"1\"/>
+    </array-list>".as_bytes()).unwrap();
+    let ranked: Vec<_> = wordnet.search_lemma("żółw", LemmaMatch::IgnoreCaseAndDiacritics).iter().map(|lu| lu.id).collect();
+    assert_eq!(ranked, vec![5, 4, 1, 2, 3]);
+}
+
+#[test]
+fn prefix_and_fuzzy_lemmas() {
+    let ids = |units: Vec<crate::LexicalUnitView>| units.iter().map(|lu| lu.id).collect::<Vec<_>>();
+    assert_eq!(ids(SAMPLE.lemmas_with_prefix("zam", 10)), vec![1, 2, 9]);
+    assert_eq!(ids(SAMPLE.lemmas_with_prefix("zam", 2)), vec![1, 2]);
+    assert_eq!(ids(SAMPLE.lemmas_with_prefix("ż", 10)), vec![5]);
+    assert!(SAMPLE.lemmas_with_prefix("x", 10).is_empty());
+
+    let fuzzy: Vec<_> = SAMPLE.fuzzy_lemmas("zamk", 2).iter().map(|(lu, d)| (lu.id, *d)).collect();
+    assert_eq!(fuzzy, vec![(1, 1), (2, 1)]);
+    let fuzzy: Vec<_> = SAMPLE.fuzzy_lemmas("żolw", 2).iter().map(|(lu, d)| (lu.id, *d)).collect();
+    assert_eq!(fuzzy, vec![(5, 2)]);
+    assert_eq!(SAMPLE.fuzzy_lemmas("gad", 0).len(), 1);
+
+    let mut xml = String::from("<array-list>");
+    for (id, name) in ["kot", "kos", "koc", "kat", "kto", "płot", "kotek", "pies"].iter().enumerate() {
+        xml += &format!(r#"<lexical-unit id="{}" name="{}"/>"#, id + 1, name);
+    }
+    xml += "</array-list>";
+    let wordnet = PlWordNet::from_bytes(xml.as_bytes()).unwrap();
+    let fuzzy: Vec<_> = wordnet.fuzzy_lemmas("kot", 1).iter().map(|(lu, d)| (lu.name, *d)).collect();
+    assert_eq!(fuzzy, vec![("kot", 0), ("kat", 1), ("koc", 1), ("kos", 1)]);
+    let fuzzy: Vec<_> = wordnet.fuzzy_lemmas("kot", 2).iter().map(|(lu, d)| (lu.name, *d)).collect();
+    assert_eq!(fuzzy, vec![("kot", 0), ("kat", 1), ("koc", 1), ("kos", 1), ("kotek", 2), ("kto", 2), ("płot", 2)]);
+    assert!(wordnet.fuzzy_lemmas("żyrafa", 2).is_empty());
+}
+
+#[test]
+fn pattern_search() {
+    let ids = |found: Vec<SearchMatch>| found.iter()
+        .map(|found| match found {
+            SearchMatch::LexicalUnit(lu) => lu.id,
+            SearchMatch::Synset(synset) => synset.id,
+        })
+        .collect::<Vec<_>>();
+    let pattern = regex::Regex::new("^zam").unwrap();
+    assert_eq!(ids(SAMPLE.search_regex(&pattern, SearchField::LexicalUnitName)), vec![1, 2, 9]);
+    let pattern = regex::Regex::new("Malbork").unwrap();
+    assert_eq!(ids(SAMPLE.search_regex(&pattern, SearchField::LexicalUnitDesc)), vec![1]);
+    assert_eq!(ids(SAMPLE.search_glob("*ek", SearchField::LexicalUnitName).unwrap()), vec![1, 2]);
+    assert_eq!(ids(SAMPLE.search_glob("g?d", SearchField::LexicalUnitName).unwrap()), vec![6]);
+    assert_eq!(ids(SAMPLE.search_glob("*obronna*", SearchField::SynsetDefinition).unwrap()), vec![101]);
+    assert!(SAMPLE.search_glob("zam.k", SearchField::LexicalUnitName).unwrap().is_empty());
+}
+
+#[test]
+fn query_language() {
+    let ids = |query: &str| SAMPLE.query_lexical_units(&query.parse().unwrap()).iter().map(|lu| lu.id).collect::<Vec<_>>();
+    assert_eq!(ids("lemma:zam*"), vec![1, 2, 9]);
+    assert_eq!(ids("lemma:zam* pos:adj"), vec![9]);
+    assert_eq!(ids("pos:noun domain:zw lemma:?ółw"), vec![5]);
+    assert_eq!(ids("domain:zw"), vec![5, 6, 7]);
+    assert_eq!(ids("lang:en"), vec![8]);
+    assert_eq!(ids("synset:102 lemma:zamek"), vec![2]);
+    assert_eq!(ids("rel:hiperonimia>synset:103"), vec![1, 10]);
+    assert_eq!(ids("rel:hiponimia<synset:103 lemma:zamek"), vec![1]);
+    assert_eq!(ids("rel:11>synset:104"), vec![2, 3]);
+    assert_eq!(ids("rel:derywacyjność>lu:1"), vec![9]);
+    assert_eq!(ids("rel:derywacyjność<lu:9"), vec![1]);
+    assert_eq!(ids("lemma:\"zamek\" lu:2"), vec![2]);
+    assert_eq!(ids("").len(), 10);
+
+    let synsets: Vec<_> = SAMPLE.query_synsets(&Query::parse("lemma:zamek").unwrap()).iter().map(|s| s.id).collect();
+    assert_eq!(synsets, vec![101, 102]);
+
+    assert_eq!(Query::parse("zamek").unwrap_err(), QueryError::MalformedTerm("zamek".into()));
+    assert_eq!(Query::parse("color:red").unwrap_err(), QueryError::UnknownKey("color".into()));
+    assert!(matches!(Query::parse("pos:noun synset:x"), Err(QueryError::BadValue { .. })));
+    assert!(matches!(Query::parse("rel:hiperonimia=synset:1"), Err(QueryError::BadValue { .. })));
+    assert_eq!(Query::parse("lemma:\"dom").unwrap_err(), QueryError::UnterminatedQuote);
+
+    let ids = |terms: &[&str]| SAMPLE.query_lexical_units(&Query::from_terms(terms).unwrap()).iter().map(|lu| lu.id).collect::<Vec<_>>();
+    assert_eq!(ids(&["lemma:zam*", "pos:adj"]), vec![9]);
+    assert!(ids(&["lemma:zamek lu:2"]).is_empty());
+    assert_eq!(ids(&[]).len(), 10);
+    assert_eq!(Query::from_terms(["pos:noun", "zamek"]).unwrap_err(), QueryError::MalformedTerm("zamek".into()));
+}
+
+#[test]
+fn domains() {
+    let turtle = SAMPLE.get_lexical_unit(5).unwrap();
+    assert_eq!(turtle.semantic_domain, Some(Domain::Animals));
+    assert_eq!(Domain::Animals.code(), "zw");
+    assert_eq!(Domain::Animals.polish_label(), "zwierzęta");
+    assert_eq!(Domain::Animals.english_label(), "animals");
+    assert_eq!(Domain::Animals.lexicographer_file(), Some("noun.animal"));
+    assert_eq!(Domain::Taxonomy.lexicographer_file(), None);
+    assert_eq!(Domain::from_code("wytw"), Some(Domain::Artifacts));
+    assert_eq!(Domain::from_code("xyz"), None);
+    assert!(Domain::all().all(|domain| Domain::from_code(domain.code()) == Some(domain)));
+    assert_eq!(Domain::Qualitative.to_string(), "jak");
+    assert_eq!(Domain::from_code("cpos"), Some(Domain::VerbsOfPossession));
+    assert_eq!(Domain::from_code("pst"), Some(Domain::Perception));
+    assert_eq!(Domain::from_code("wsz"), Some(Domain::Unspecified));
+    let codes = [
+        "bhp", "czy", "wytw", "cech", "czc", "umy", "por", "zdarz", "czuj", "jedz", "grp", "msc", "cel", "rz",
+        "os", "zj", "rsl", "pos", "prc", "il", "zw", "sys", "wsz", "st", "sbst", "czas", "zwz", "ksz", "hig",
+        "zmn", "cumy", "cpor", "wal", "cjedz", "dtk", "cwytw", "cczuj", "ruch", "pst", "cpos", "sp", "cst",
+        "pog", "jak", "rel", "odcz",
+    ];
+    assert!(codes.iter().all(|code| Domain::from_code(code).is_some()));
+    assert_eq!(Domain::all().count(), codes.len());
+    // every domain used in the sample data is recognised
+    assert!(SAMPLE.lexical_units.values().all(|lu| lu.domain.is_empty() || lu.semantic_domain.is_some()));
+
+    let ids = |domain| SAMPLE.lexical_units_in_domain(domain).map(|lu| lu.id).collect::<Vec<_>>();
+    assert_eq!(ids(Domain::Animals), vec![5, 6, 7]);
+    assert_eq!(ids(Domain::Qualitative), vec![9]);
+    assert!(ids(Domain::Plants).is_empty());
+}
+
+#[test]
+fn workstate_filtering() {
+    assert_eq!(SAMPLE.get_lexical_unit(1).unwrap().state, &Workstate::Verified);
+    assert_eq!(SAMPLE.get_synset(108).unwrap().state, &Workstate::Unprocessed);
+    assert_eq!(Workstate::from_name("Sprawdzone"), Workstate::Verified);
+    assert_eq!(Workstate::from_name("Nowe"), Workstate::Other("Nowe".into()));
+    assert_eq!(Workstate::Unprocessed.to_string(), "Nie przetworzone");
+    assert_eq!(Workstate::Other("Nowe".into()).name(), "Nowe");
+
+    let wordnet = LoadOptions::new()
+        .workstates([Workstate::Verified])
+        .load_bytes(include_bytes!("../fixtures/plwordnet_sample.xml"))
+        .unwrap();
+    let metadata = wordnet.get_metadata();
+    assert_eq!(metadata.lexical_units, 9);
+    assert_eq!(metadata.synsets, 9);
+    assert_eq!(metadata.synset_relations, 14);
+    assert!(wordnet.get_lexical_unit(8).is_none());
+    assert!(wordnet.get_synset(108).is_none());
+    assert_eq!(wordnet.lexical_units_by_lemma("castle", None).count(), 0);
+    assert!(wordnet.english_equivalents(101).is_empty());
+    assert_eq!(wordnet.synset_of(1).unwrap().id, 101);
+    assert_eq!(wordnet.depth(101), Some(2));
+    assert_eq!(LoadOptions::new().load_bytes(include_bytes!("../fixtures/plwordnet_sample.xml")).unwrap().get_metadata().lexical_units, 10);
+
+    let xml = br#"<array-list>
+        <lexical-unit id="1" name="a" workstate="Sprawdzone"/>
+        <lexical-unit id="2" name="b" workstate="Nowe"/>
+        <lexical-unit id="3" name="c" workstate="Sprawdzone"/>
+        <lexical-unit id="4" name="d" workstate="Sprawdzone"/>
+        <synset id="1" workstate="Nowe"><unit-id>4</unit-id></synset>
+        <lexicalrelations parent="1" child="2" relation="1" valid="true"/>
+        <lexicalrelations parent="1" child="3" relation="1" valid="false"/>
+    </array-list>"#;
+    let children = |wordnet: &PlWordNet| wordnet.lexical_relations_from(1, []).map(|lr| lr.child.unwrap().id).collect::<Vec<_>>();
+    let wordnet = LoadOptions::new().valid_relations_only(true).load_bytes(xml).unwrap();
+    assert_eq!(children(&wordnet), vec![2]);
+    assert_eq!(wordnet.get_lexical_unit(2).unwrap().state, &Workstate::Other("Nowe".into()));
+    let wordnet = LoadOptions::new().workstates([Workstate::Verified]).load_bytes(xml).unwrap();
+    assert_eq!(children(&wordnet), vec![3]);
+    assert_eq!(wordnet.get_lexical_unit(4).unwrap().synset_id, None);
+    let wordnet = LoadOptions::new().workstates([Workstate::Other("Nowe".into())]).load_bytes(xml).unwrap();
+    assert_eq!(wordnet.get_metadata().lexical_units, 1);
+    assert!(wordnet.get_synset(1).unwrap().lexical_units.is_empty());
+}
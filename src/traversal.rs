@@ -0,0 +1,285 @@
+use std::collections::{HashSet, VecDeque};
+use crate::model::{PlWordNet, RelationTypeView, SynsetView};
+
+
+type VisitPredicate<'a> = Box<dyn Fn(&SynsetView<'a>) -> bool + 'a>;
+
+
+/// Represents the direction in which synset relations are followed during a traversal.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum Direction {
+    /// Follows relations from their parent to their child.
+    #[default]
+    Outgoing,
+    /// Follows relations from their child to their parent.
+    Incoming,
+    /// Follows relations in both directions.
+    Both,
+}
+
+/// Represents the order in which synsets are visited during a traversal.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum TraversalOrder {
+    /// Visits all synsets at a given depth before moving deeper.
+    #[default]
+    BreadthFirst,
+    /// Follows each branch as deep as possible before backtracking.
+    DepthFirst,
+}
+
+/// Builder for traversals over the synset relation graph, created with [`PlWordNet::traverse`].
+///
+/// Iterating over a traversal yields `(depth, SynsetView, RelationTypeView)` tuples, where the
+/// relation type is the one of the relation the synset was reached through. The starting synset
+/// is not yielded, and each synset is visited at most once.
+pub struct Traversal<'a> {
+    wordnet: &'a PlWordNet,
+    start: usize,
+    order: TraversalOrder,
+    relations: EdgeFilter,
+    direction: Direction,
+    edges: Option<(HashSet<usize>, HashSet<usize>)>,
+    max_depth: Option<usize>,
+    predicate: Option<VisitPredicate<'a>>,
+}
+
+impl<'a> Traversal<'a> {
+    /// Visits synsets in breadth-first order. This is the default.
+    pub fn breadth_first(mut self) -> Self {
+        self.order = TraversalOrder::BreadthFirst;
+        self
+    }
+
+    /// Visits synsets in depth-first order.
+    pub fn depth_first(mut self) -> Self {
+        self.order = TraversalOrder::DepthFirst;
+        self
+    }
+
+    /// Sets the order in which synsets are visited.
+    pub fn order(mut self, order: TraversalOrder) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// Restricts the traversal to relations of the given relation type IDs. By default, or when
+    /// no IDs are given, relations of every type are followed.
+    pub fn relations(mut self, relations: impl IntoIterator<Item=usize>) -> Self {
+        self.relations = EdgeFilter::from_relations(relations);
+        self
+    }
+
+    /// Sets the direction in which relations are followed. Defaults to [`Direction::Outgoing`].
+    pub fn direction(mut self, direction: Direction) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Limits the traversal to synsets at most `max_depth` relations away from the start.
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Only visits synsets for which `predicate` returns `true`. Synsets that are rejected are
+    /// neither yielded nor expanded.
+    pub fn filter(mut self, predicate: impl Fn(&SynsetView<'a>) -> bool + 'a) -> Self {
+        self.predicate = Some(Box::new(predicate));
+        self
+    }
+
+    /// Follows outgoing relations of the `outgoing` types and incoming relations of the
+    /// `incoming` types, overriding `relations` and `direction`.
+    pub(crate) fn follow(mut self, outgoing: HashSet<usize>, incoming: HashSet<usize>) -> Self {
+        self.edges = Some((outgoing, incoming));
+        self
+    }
+}
+
+impl<'a> IntoIterator for Traversal<'a> {
+    type Item = (usize, SynsetView<'a>, RelationTypeView<'a>);
+    type IntoIter = TraversalIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let (outgoing, incoming) = match self.edges {
+            Some((outgoing, incoming)) => (Some(EdgeFilter::Only(outgoing)), Some(EdgeFilter::Only(incoming))),
+            None => {
+                match self.direction {
+                    Direction::Outgoing => (Some(self.relations), None),
+                    Direction::Incoming => (None, Some(self.relations)),
+                    Direction::Both => (Some(self.relations.clone()), Some(self.relations)),
+                }
+            }
+        };
+        let mut iter = TraversalIter {
+            wordnet: self.wordnet,
+            order: self.order,
+            outgoing,
+            incoming,
+            max_depth: self.max_depth,
+            predicate: self.predicate,
+            pending: VecDeque::new(),
+            visited: HashSet::from([self.start]),
+        };
+        if self.wordnet.synsets.contains_key(&self.start) {
+            iter.expand(0, self.start);
+        }
+        iter
+    }
+}
+
+/// Represents the relation types accepted when following relations.
+#[derive(Clone)]
+pub(crate) enum EdgeFilter {
+    All,
+    Only(HashSet<usize>),
+}
+
+impl EdgeFilter {
+    /// Accepts the given relation type IDs, or every type if none are given.
+    pub(crate) fn from_relations(relations: impl IntoIterator<Item=usize>) -> Self {
+        let relations: HashSet<_> = relations.into_iter().collect();
+        match relations.is_empty() {
+            true => EdgeFilter::All,
+            false => EdgeFilter::Only(relations),
+        }
+    }
+
+    pub(crate) fn accepts(&self, relation: usize) -> bool {
+        match self {
+            EdgeFilter::All => true,
+            EdgeFilter::Only(relations) => relations.contains(&relation),
+        }
+    }
+}
+
+/// Iterator over the synsets visited by a [`Traversal`].
+pub struct TraversalIter<'a> {
+    wordnet: &'a PlWordNet,
+    order: TraversalOrder,
+    outgoing: Option<EdgeFilter>,
+    incoming: Option<EdgeFilter>,
+    max_depth: Option<usize>,
+    predicate: Option<VisitPredicate<'a>>,
+    /// Synsets waiting to be visited as `(depth, synset id, relation type id)`.
+    pending: VecDeque<(usize, usize, usize)>,
+    visited: HashSet<usize>,
+}
+
+impl TraversalIter<'_> {
+    fn expand(&mut self, depth: usize, synset_id: usize) {
+        if self.max_depth.is_some_and(|max| depth >= max) {
+            return;
+        }
+        let wn = self.wordnet;
+        let (outgoing, incoming) = (&self.outgoing, &self.incoming);
+        let next: Vec<_> = wn.synset_edges(
+                synset_id,
+                |relation| outgoing.as_ref().is_some_and(|filter| filter.accepts(relation)),
+                |relation| incoming.as_ref().is_some_and(|filter| filter.accepts(relation)),
+            )
+            .map(|(id, i)| (id, wn.synset_relations[i].relation))
+            .filter(|(id, relation)| wn.synsets.contains_key(id) && wn.relation_types.contains_key(relation))
+            .filter(|(id, _)| !self.visited.contains(id))
+            .collect();
+        match self.order {
+            // a synset is reached at its smallest depth first, so it is marked when enqueued
+            TraversalOrder::BreadthFirst => {
+                for (id, relation) in next {
+                    if self.visited.insert(id) {
+                        self.pending.push_back((depth + 1, id, relation));
+                    }
+                }
+            },
+            TraversalOrder::DepthFirst => {
+                self.pending.extend(next.into_iter().rev().map(|(id, relation)| (depth + 1, id, relation)));
+            },
+        }
+    }
+}
+
+impl<'a> Iterator for TraversalIter<'a> {
+    type Item = (usize, SynsetView<'a>, RelationTypeView<'a>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (depth, id, relation) = match self.order {
+                TraversalOrder::BreadthFirst => self.pending.pop_front()?,
+                TraversalOrder::DepthFirst => {
+                    let pending = self.pending.pop_back()?;
+                    if !self.visited.insert(pending.1) {
+                        continue;
+                    }
+                    pending
+                },
+            };
+            let (Some(synset), Some(relation)) = (self.wordnet.get_synset(id), self.wordnet.get_relation_type(relation)) else {
+                continue;
+            };
+            if self.predicate.as_ref().is_some_and(|predicate| !predicate(&synset)) {
+                continue;
+            }
+            self.expand(depth, id);
+            return Some((depth, synset, relation));
+        }
+    }
+}
+
+impl PlWordNet {
+    /// Starts building a traversal of the synset relation graph from the synset with the
+    /// specified ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `synset_id` - The identifier of the synset the traversal starts from.
+    ///
+    /// # Returns
+    ///
+    /// A `Traversal` builder which follows outgoing relations of every type in breadth-first order
+    /// unless configured otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use plwordnet::{PlWordNet};
+    ///
+    /// let plwordnet = PlWordNet::from_file("plwordnet_4_2.xml").unwrap();
+    /// let meronymy = plwordnet.relation_type_by_name("meronimia").unwrap();
+    /// for (depth, synset, via) in plwordnet.traverse(1234).relations([meronymy.id]).max_depth(3) {
+    ///     println!("{} {} ({})", depth, synset.id, via.name);
+    /// }
+    /// ```
+    pub fn traverse(&self, synset_id: usize) -> Traversal<'_> {
+        Traversal {
+            wordnet: self,
+            start: synset_id,
+            order: TraversalOrder::default(),
+            relations: EdgeFilter::All,
+            direction: Direction::default(),
+            edges: None,
+            max_depth: None,
+            predicate: None,
+        }
+    }
+
+    /// Synsets connected to a synset by an outgoing relation whose type satisfies `outgoing` or an
+    /// incoming relation whose type satisfies `incoming`, with the index of that relation.
+    pub(crate) fn synset_edges<'s>(
+        &'s self,
+        synset_id: usize,
+        outgoing: impl Fn(usize) -> bool + 's,
+        incoming: impl Fn(usize) -> bool + 's,
+    ) -> impl Iterator<Item=(usize, usize)> + 's {
+        let children = self.synset_relations_out.get(&synset_id)
+            .into_iter()
+            .flatten()
+            .filter(move |&&i| outgoing(self.synset_relations[i].relation))
+            .map(|&i| (self.synset_relations[i].child, i));
+        let parents = self.synset_relations_in.get(&synset_id)
+            .into_iter()
+            .flatten()
+            .filter(move |&&i| incoming(self.synset_relations[i].relation))
+            .map(|&i| (self.synset_relations[i].parent, i));
+        children.chain(parents)
+    }
+}
@@ -0,0 +1,110 @@
+use std::collections::HashSet;
+use std::io::BufRead;
+use std::path::Path;
+use crate::model::{PlWordNet, PlWordNetError, Workstate};
+use crate::parser::{from_file, from_reader};
+
+
+/// Builder for loading plWordNet with entries left out, for example to work with verified data
+/// only.
+///
+/// By default every entry is loaded, exactly as with [`PlWordNet::from_file`].
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use plwordnet::{LoadOptions, Workstate};
+///
+/// let plwordnet = LoadOptions::new()
+///     .workstates([Workstate::Verified])
+///     .valid_relations_only(true)
+///     .load_file("plwordnet_4_2.xml")
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct LoadOptions {
+    workstates: Option<HashSet<Workstate>>,
+    valid_relations_only: bool,
+}
+
+impl LoadOptions {
+    /// Creates options which load every entry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only loads lexical units and synsets whose workstate is one of `workstates`, together with
+    /// the relations between them. Workstates which are not recognised are matched through
+    /// [`Workstate::Other`].
+    ///
+    /// Lexical units are removed from the synsets which are left out, and lexical units of a
+    /// synset which is left out do not belong to any synset.
+    pub fn workstates(mut self, workstates: impl IntoIterator<Item=Workstate>) -> Self {
+        self.workstates = Some(workstates.into_iter().collect());
+        self
+    }
+
+    /// Leaves out lexical and synset relations which are not marked as valid.
+    pub fn valid_relations_only(mut self, valid_only: bool) -> Self {
+        self.valid_relations_only = valid_only;
+        self
+    }
+
+    /// Loads the plWordNet XML file from the specified path, like [`PlWordNet::from_file`].
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to the plWordNet XML file.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<PlWordNet, PlWordNetError>` - The loaded `PlWordNet` instance, or an error describing why loading failed.
+    pub fn load_file(&self, path: impl AsRef<Path>) -> Result<PlWordNet, PlWordNetError> {
+        from_file(path.as_ref(), self)
+    }
+
+    /// Loads plWordNet XML from a buffered reader, like [`PlWordNet::from_reader`].
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - A buffered reader yielding the plWordNet XML document.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<PlWordNet, PlWordNetError>` - The loaded `PlWordNet` instance, or an error describing why loading failed.
+    pub fn load_reader<R: BufRead>(&self, reader: R) -> Result<PlWordNet, PlWordNetError> {
+        from_reader(reader, self)
+    }
+
+    /// Loads plWordNet XML held in memory, like [`PlWordNet::from_bytes`].
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The contents of a plWordNet XML document.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<PlWordNet, PlWordNetError>` - The loaded `PlWordNet` instance, or an error describing why loading failed.
+    pub fn load_bytes(&self, bytes: &[u8]) -> Result<PlWordNet, PlWordNetError> {
+        from_reader(bytes, self)
+    }
+
+    /// Removes the entries these options leave out from a freshly parsed plWordNet, before its
+    /// indexes are built.
+    pub(crate) fn retain(&self, wn: &mut PlWordNet) {
+        if let Some(workstates) = &self.workstates {
+            wn.lexical_units.retain(|_, lu| workstates.contains(&lu.state));
+            wn.synsets.retain(|_, synset| workstates.contains(&synset.state));
+            let (units, synsets) = (&wn.lexical_units, &wn.synsets);
+            wn.lexical_relations.retain(|lr| units.contains_key(&lr.parent) && units.contains_key(&lr.child));
+            wn.synset_relations.retain(|sr| synsets.contains_key(&sr.parent) && synsets.contains_key(&sr.child));
+            for synset in wn.synsets.values_mut() {
+                synset.lexical_units.retain(|id| units.contains_key(id));
+            }
+        }
+        if self.valid_relations_only {
+            wn.lexical_relations.retain(|lr| lr.valid);
+            wn.synset_relations.retain(|sr| sr.valid);
+        }
+    }
+}